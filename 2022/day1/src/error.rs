@@ -0,0 +1,61 @@
+use std::{error, fmt};
+
+use aoc_core::LocatedError;
+use aoc_input::Position;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    InvalidCalories {
+        position: Position,
+        found: String,
+    },
+    NegativeCalories {
+        position: Position,
+        found: String,
+    },
+    Overflow {
+        position: Position,
+    },
+    NoElves,
+    Unreadable {
+        line: Option<usize>,
+        message: String,
+    },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::InvalidCalories { found, .. } => {
+                write!(f, "expected a number of calories, found '{found}'")
+            }
+            ParseError::NegativeCalories { found, .. } => {
+                write!(f, "expected a positive number of calories, found '{found}'")
+            }
+            ParseError::Overflow { .. } => {
+                write!(
+                    f,
+                    "expected the calories of an elf to fit in 64 bits, found an overflow"
+                )
+            }
+            ParseError::NoElves => write!(f, "no elves found in the input"),
+            ParseError::Unreadable { message, .. } => {
+                write!(f, "unable to read the input: {message}")
+            }
+        }
+    }
+}
+
+impl error::Error for ParseError {}
+
+impl LocatedError for ParseError {
+    fn position(&self) -> Option<Position> {
+        match self {
+            ParseError::InvalidCalories { position, .. } => Some(*position),
+            ParseError::NegativeCalories { position, .. } => Some(*position),
+            ParseError::Overflow { position } => Some(*position),
+            ParseError::NoElves => None,
+            ParseError::Unreadable { line, .. } => line.map(|line| Position { line, column: 1 }),
+        }
+    }
+}
@@ -0,0 +1,500 @@
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+use std::io::BufRead;
+use std::num::IntErrorKind;
+
+use aoc_core::{Answer, Solution};
+use aoc_input::{ErrorKind, GroupedLines, Input, Line};
+
+mod error;
+mod stats;
+
+pub use error::ParseError;
+pub use stats::{ItemStats, Stats};
+
+pub struct Day1;
+
+// The (calories, id) pair of every elf.
+pub type Elves = Vec<(u64, usize)>;
+
+// n comes from the user, so only this much is allocated up front.
+const MAX_PREALLOCATED: usize = 1024;
+
+// Keeps only the n largest values in a min-heap, so memory does not grow with the input.
+struct Top<T> {
+    n: usize,
+    heap: BinaryHeap<Reverse<T>>,
+}
+
+impl<T: Ord> Top<T> {
+    fn new(n: usize) -> Top<T> {
+        Top {
+            n,
+            heap: BinaryHeap::with_capacity(n.min(MAX_PREALLOCATED) + 1),
+        }
+    }
+
+    fn push(&mut self, val: T) {
+        self.heap.push(Reverse(val));
+        if self.heap.len() > self.n {
+            self.heap.pop();
+        }
+    }
+
+    // Sorted from largest to smallest.
+    fn into_sorted(self) -> Vec<T> {
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse(val)| val)
+            .collect()
+    }
+}
+
+// The result is sorted from most to least calories, on a tie the lowest id comes first.
+pub fn top_n(elves_cal: impl IntoIterator<Item = (u64, usize)>, n: usize) -> Vec<(u64, usize)> {
+    let mut top = Top::new(n);
+    for (val, id) in elves_cal {
+        top.push((val, Reverse(id)));
+    }
+    top.into_sorted()
+        .into_iter()
+        .map(|(val, Reverse(id))| (val, id))
+        .collect()
+}
+
+pub fn top_elves(elves_cal: &[(u64, usize)], n: usize) -> Vec<(u64, usize)> {
+    top_n(elves_cal.iter().copied(), n)
+}
+
+// Number of food items of every elf, in the same order as the elves from parse.
+pub fn item_counts(input: &Input) -> Vec<usize> {
+    input.groups().iter().map(Vec::len).collect()
+}
+
+// The calories of one elf so far, the lines that are not a number are reported.
+// The total is checked, after an overflow the rest of the elf is still checked
+// but not added anymore.
+#[derive(Default)]
+struct ElfTotal {
+    calories: u64,
+    overflowed: bool,
+}
+
+impl ElfTotal {
+    fn add(&mut self, l: &Line, errors: &mut Vec<ParseError>) {
+        let cal = match l.text.parse::<u64>() {
+            Ok(cal) => cal,
+            Err(e) => {
+                errors.push(calories_error(l, e.kind()));
+                return;
+            }
+        };
+        if self.overflowed {
+            return;
+        }
+
+        match self.calories.checked_add(cal) {
+            Some(total) => self.calories = total,
+            None => {
+                errors.push(ParseError::Overflow {
+                    position: l.start(),
+                });
+                self.calories = u64::MAX;
+                self.overflowed = true;
+            }
+        }
+    }
+}
+
+fn elf_total<'a>(group: impl IntoIterator<Item = Line<'a>>, errors: &mut Vec<ParseError>) -> u64 {
+    let mut total = ElfTotal::default();
+    for l in group {
+        total.add(&l, errors);
+    }
+    total.calories
+}
+
+fn calories_error(l: &Line, kind: &IntErrorKind) -> ParseError {
+    let found = l.text.to_owned();
+    let is_number = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+    match l.text.strip_prefix('-') {
+        Some(digits) if is_number(digits) => ParseError::NegativeCalories {
+            position: l.start(),
+            found,
+        },
+        _ if *kind == IntErrorKind::PosOverflow => ParseError::Overflow {
+            position: l.start(),
+        },
+        _ => ParseError::InvalidCalories {
+            position: l.start(),
+            found,
+        },
+    }
+}
+
+// The sum of the n largest elves, this can't overflow as u128.
+pub fn top_sum(calories: impl IntoIterator<Item = u64>) -> u128 {
+    calories.into_iter().map(u128::from).sum()
+}
+
+// An elf read from one of several inputs, source is the index of the input
+// in the order they were read and the lines are where the elf was defined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Elf {
+    pub calories: u64,
+    pub id: usize,
+    pub source: usize,
+    pub first_line: usize,
+    pub last_line: usize,
+}
+
+// Ordered like the (calories, id) pairs, on a tie the lowest id is the largest.
+impl Ord for Elf {
+    fn cmp(&self, other: &Elf) -> Ordering {
+        (self.calories, Reverse(self.id)).cmp(&(other.calories, Reverse(other.id)))
+    }
+}
+
+impl PartialOrd for Elf {
+    fn partial_cmp(&self, other: &Elf) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// Streams the elves of one or more inputs, only the lines of a single elf and the
+// n largest elves are kept in memory. The elves are numbered over all the inputs.
+pub struct TopElves {
+    top: Top<Elf>,
+    elf_count: usize,
+    source_count: usize,
+}
+
+impl TopElves {
+    pub fn new(n: usize) -> TopElves {
+        TopElves {
+            top: Top::new(n),
+            elf_count: 0,
+            source_count: 0,
+        }
+    }
+
+    // Like parse, all the errors of the input are collected instead of stopping at the first one.
+    pub fn read(&mut self, source_name: &str, reader: impl BufRead) -> Result<(), Vec<ParseError>> {
+        let source = self.source_count;
+        self.source_count += 1;
+
+        let mut errors = Vec::new();
+        // (total, first line, last line) of the elf that is being read, an elf is summed
+        // line by line so it doesn't have to fit in memory
+        let mut current: Option<(ElfTotal, usize, usize)> = None;
+        for line in GroupedLines::new(source_name, reader) {
+            let line = match line {
+                Ok(line) => line,
+                Err(e) => {
+                    // only the reason, the message of the error already says the
+                    // input can't be read
+                    let message = match e.kind {
+                        ErrorKind::Io(e) => e.to_string(),
+                        kind => kind.to_string(),
+                    };
+                    errors.push(ParseError::Unreadable {
+                        line: e.line,
+                        message,
+                    });
+                    break;
+                }
+            };
+
+            if line.new_group {
+                if let Some(elf) = current.take() {
+                    self.push(source, elf);
+                }
+            }
+            let number = line.line.number;
+            let (total, _, last_line) =
+                current.get_or_insert_with(|| (ElfTotal::default(), number, number));
+            total.add(&line.line.as_line(), &mut errors);
+            *last_line = number;
+        }
+        if let Some(elf) = current {
+            self.push(source, elf);
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    fn push(&mut self, source: usize, (total, first_line, last_line): (ElfTotal, usize, usize)) {
+        self.top.push(Elf {
+            calories: total.calories,
+            id: self.elf_count,
+            source,
+            first_line,
+            last_line,
+        });
+        self.elf_count += 1;
+    }
+
+    // Sorted from most to least calories.
+    pub fn finish(self) -> Result<Vec<Elf>, ParseError> {
+        if self.elf_count == 0 {
+            return Err(ParseError::NoElves);
+        }
+        Ok(self.top.into_sorted())
+    }
+}
+
+pub fn stream_top_elves(
+    source_name: &str,
+    reader: impl BufRead,
+    n: usize,
+) -> Result<Vec<(u64, usize)>, Vec<ParseError>> {
+    let mut top = TopElves::new(n);
+    top.read(source_name, reader)?;
+    let elves = top.finish().map_err(|e| vec![e])?;
+    Ok(elves.iter().map(|elf| (elf.calories, elf.id)).collect())
+}
+
+impl Solution for Day1 {
+    type Input = Elves;
+    type Error = ParseError;
+
+    fn parse(input: &Input) -> Result<Elves, Vec<ParseError>> {
+        let mut elves_cal = Vec::new();
+        let mut errors = Vec::new();
+        // every group of lines between empty lines is an elf
+        for group in input.groups() {
+            let total_cal_elf = elf_total(group, &mut errors);
+            let id = elves_cal.len();
+            log::debug!("Added elf(id={id}) with {total_cal_elf} calories");
+            elves_cal.push((total_cal_elf, id));
+        }
+
+        if elves_cal.is_empty() {
+            errors.push(ParseError::NoElves);
+        }
+
+        if errors.is_empty() {
+            Ok(elves_cal)
+        } else {
+            Err(errors)
+        }
+    }
+
+    fn part1(elves_cal: &Elves) -> Answer {
+        let (max_val, _max_id) = elves_cal
+            .iter()
+            .max_by_key(|(val, _index)| val)
+            .expect("parsing makes sure there is at least one elf");
+
+        Answer::from(*max_val)
+    }
+
+    fn part2(elves_cal: &Elves) -> Answer {
+        let top3_cals = top_sum(top_elves(elves_cal, 3).iter().map(|(val, _)| *val));
+
+        Answer::from(top3_cals)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use aoc_core::format_error_in;
+    use aoc_input::Position;
+    use std::path::Path;
+
+    #[test]
+    fn test_top_n() {
+        let elves = vec![(5, 0), (9, 1), (5, 2), (1, 3), (7, 4)];
+        assert_eq!(top_n(elves.clone(), 3), vec![(9, 1), (7, 4), (5, 0)]);
+        assert_eq!(top_n(elves.clone(), 10).len(), 5);
+        assert!(top_n(elves.clone(), 0).is_empty());
+        // a huge n is not allocated up front
+        assert_eq!(top_n(elves.clone(), usize::MAX).len(), 5);
+        assert_eq!(top_n(elves, 1_000_000_000_000).len(), 5);
+    }
+
+    #[test]
+    fn test_stream_top_elves() {
+        let text =
+            std::fs::read_to_string(Path::new(env!("CARGO_MANIFEST_DIR")).join("example.txt"))
+                .unwrap();
+        let top = stream_top_elves("example", text.as_bytes(), 3).unwrap();
+        assert_eq!(top, vec![(24000, 3), (11000, 2), (10000, 4)]);
+        assert_eq!(
+            top,
+            top_elves(&aoc_core::example::<Day1>(env!("CARGO_MANIFEST_DIR")), 3)
+        );
+
+        // the last elf does not need a blank line after it
+        assert_eq!(
+            stream_top_elves("test", "1\n2\n\n4".as_bytes(), 1).unwrap(),
+            vec![(4, 1)]
+        );
+
+        let errors = stream_top_elves("test", "1\nx\n\n".as_bytes(), 1).unwrap_err();
+        assert_eq!(
+            errors,
+            vec![ParseError::InvalidCalories {
+                position: Position { line: 2, column: 1 },
+                found: "x".to_owned(),
+            }]
+        );
+        assert_eq!(
+            stream_top_elves("test", "\n".as_bytes(), 1).unwrap_err(),
+            vec![ParseError::NoElves]
+        );
+
+        // one elf with a lot of lines is summed while it is read
+        let text = "1\n".repeat(100_000);
+        assert_eq!(
+            stream_top_elves("test", text.as_bytes(), 1).unwrap(),
+            vec![(100_000, 0)]
+        );
+    }
+
+    #[test]
+    fn test_messy_input() {
+        // the last elf is not followed by a blank line, and the file was saved on windows
+        let text = "1000\r\n2000 \r\n\r\n \r\n\r\n3000\r\n4000";
+        let elves = Day1::parse(&Input::from_string("test", text)).unwrap();
+        assert_eq!(elves, vec![(3000, 0), (7000, 1)]);
+        assert_eq!(
+            stream_top_elves("test", text.as_bytes(), 2).unwrap(),
+            vec![(7000, 1), (3000, 0)]
+        );
+    }
+
+    #[test]
+    fn test_multiple_sources() {
+        let mut top = TopElves::new(2);
+        top.read("a", "1\n\n5\n".as_bytes()).unwrap();
+        top.read("b", "\n3\n4\n\n2".as_bytes()).unwrap();
+        let elves = top.finish().unwrap();
+
+        assert_eq!(
+            elves,
+            vec![
+                Elf {
+                    calories: 7,
+                    id: 2,
+                    source: 1,
+                    first_line: 2,
+                    last_line: 3,
+                },
+                Elf {
+                    calories: 5,
+                    id: 1,
+                    source: 0,
+                    first_line: 3,
+                    last_line: 3,
+                },
+            ]
+        );
+        assert_eq!(TopElves::new(2).finish(), Err(ParseError::NoElves));
+    }
+
+    #[test]
+    fn test_unreadable() {
+        struct Broken;
+        impl std::io::Read for Broken {
+            fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+                Err(std::io::Error::other("disk is gone"))
+            }
+        }
+
+        let errors = stream_top_elves("test", std::io::BufReader::new(Broken), 1).unwrap_err();
+        assert_eq!(
+            format_error_in("test", &errors[0]),
+            "test:1:1: unable to read the input: disk is gone"
+        );
+    }
+
+    #[test]
+    fn test_overflow() {
+        let max = u64::MAX.to_string();
+        let input = Input::from_string("test", format!("{max}\n\n{max}\n\n{max}\n"));
+        let elves = Day1::parse(&input).unwrap();
+        assert_eq!(Day1::part1(&elves), Answer::from(u64::MAX));
+        assert_eq!(
+            Day1::part2(&elves).to_string(),
+            (3 * u128::from(u64::MAX)).to_string()
+        );
+
+        let input = Input::from_string("test", format!("1\n{max}\n\n18446744073709551616"));
+        assert_eq!(
+            Day1::parse(&input).unwrap_err(),
+            vec![
+                ParseError::Overflow {
+                    position: Position { line: 2, column: 1 }
+                },
+                ParseError::Overflow {
+                    position: Position { line: 4, column: 1 }
+                },
+            ]
+        );
+
+        // the lines after an overflow are still checked
+        let text = format!("{max}\n1\nx\n-3\n{max}\n");
+        let expected = vec![
+            ParseError::Overflow {
+                position: Position { line: 2, column: 1 },
+            },
+            ParseError::InvalidCalories {
+                position: Position { line: 3, column: 1 },
+                found: "x".to_owned(),
+            },
+            ParseError::NegativeCalories {
+                position: Position { line: 4, column: 1 },
+                found: "-3".to_owned(),
+            },
+        ];
+        assert_eq!(
+            Day1::parse(&Input::from_string("test", text.as_str())).unwrap_err(),
+            expected
+        );
+        assert_eq!(
+            stream_top_elves("test", text.as_bytes(), 1).unwrap_err(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_negative_calories() {
+        let input = Input::from_string("test", "100\n-5\n\n-\n");
+        assert_eq!(
+            Day1::parse(&input).unwrap_err(),
+            vec![
+                ParseError::NegativeCalories {
+                    position: Position { line: 2, column: 1 },
+                    found: "-5".to_owned(),
+                },
+                ParseError::InvalidCalories {
+                    position: Position { line: 4, column: 1 },
+                    found: "-".to_owned(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_example_part1() {
+        assert_eq!(
+            Day1::part1(&aoc_core::example::<Day1>(env!("CARGO_MANIFEST_DIR"))),
+            Answer::from(24000)
+        );
+    }
+
+    #[test]
+    fn test_example_part2() {
+        assert_eq!(
+            Day1::part2(&aoc_core::example::<Day1>(env!("CARGO_MANIFEST_DIR"))),
+            Answer::from(45000)
+        );
+    }
+}
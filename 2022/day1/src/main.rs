@@ -1,76 +1,132 @@
-//use clap::{Arg, App, SubCommand};
-use clap::Parser;
-use std::borrow::Borrow;
+use std::collections::HashSet;
 use std::fs::File;
-use std::io::{self, BufRead, BufReader, Lines};
+use std::io::BufReader;
+use std::path::PathBuf;
+
+use aoc_core::{format_error_in, init_logging, parse_or_exit, read_or_exit};
+use clap::Parser;
+use day1::{item_counts, top_sum, Day1, Stats, TopElves};
 
 #[derive(Parser, Debug)]
 struct Args {
+    /// Calorie files or glob patterns like "logs/*.txt", can be repeated
+    #[arg(short, long = "path", required = true, num_args = 1..)]
+    paths: Vec<String>,
+
+    /// Number of elves with the most calories to report
+    #[arg(short, long, default_value_t = 3, value_parser = clap::value_parser!(u64).range(1..))]
+    top: u64,
+
+    /// Print statistics of all the elves, this reads the whole input in memory
     #[arg(short, long)]
-    path: std::path::PathBuf,
+    stats: bool,
+
+    /// Number of buckets in the histogram of the statistics, at most 1000
+    #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u64).range(1..=1000))]
+    buckets: u64,
+
+    /// Show more logging, can be repeated
+    #[arg(short, long, action = clap::ArgAction::Count)]
+    verbose: u8,
 }
 
-fn read_lines(filename: &std::path::Path) -> io::Result<Lines<BufReader<File>>> {
-    let file = File::open(filename)?;
-    let reader = BufReader::new(file);
-    let lines = reader.lines();
-    Ok(lines)
+// Every pattern has to match at least one file, the matches of a pattern are sorted.
+// A file that matches more than once is only read the first time, so its elves
+// aren't counted twice.
+fn expand_paths(patterns: &[String]) -> Result<Vec<PathBuf>, String> {
+    let mut paths = Vec::new();
+    let mut seen = HashSet::new();
+    for pattern in patterns {
+        let matches =
+            glob::glob(pattern).map_err(|e| format!("{pattern}: invalid pattern: {e}"))?;
+        let mut found = false;
+        for path in matches {
+            let path = path.map_err(|e| e.to_string())?;
+            found = true;
+            // input.txt and ./input.txt are the same file
+            let key = std::fs::canonicalize(&path).unwrap_or_else(|_| path.clone());
+            if seen.insert(key) {
+                paths.push(path);
+            }
+        }
+        if !found {
+            return Err(format!("{pattern}: no such file"));
+        }
+    }
+
+    Ok(paths)
+}
+
+fn print_stats(paths: &[PathBuf], buckets: usize) {
+    // The ids continue over the files, like when the top elves are streamed.
+    let mut elves_cal = Vec::new();
+    let mut items = Vec::new();
+    for path in paths {
+        let input = read_or_exit(path);
+        let offset = elves_cal.len();
+        let elves = parse_or_exit::<Day1>(&input);
+        elves_cal.extend(elves.into_iter().map(|(val, id)| (val, id + offset)));
+        items.extend(item_counts(&input));
+    }
+
+    let stats = Stats::new(&elves_cal, &items, buckets)
+        .expect("parsing makes sure there is at least one elf");
+    print!("{stats}");
 }
 
 fn main() {
     let args = Args::parse();
+    init_logging(args.verbose);
 
-    if !args.path.exists() {
-        let pathstring =
-            args.path.into_os_string().into_string().expect(
-                "Invalid filepath, the path doesn't exist, and is not convertable to string",
-            );
-        panic!(".filepath {pathstring} does not exist.");
+    let paths = expand_paths(&args.paths).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        std::process::exit(1);
+    });
+    let names: Vec<String> = paths.iter().map(|p| p.display().to_string()).collect();
+
+    if args.stats {
+        print_stats(&paths, args.buckets as usize);
+        return;
     }
 
-    if let Ok(lines) = read_lines(&args.path.as_path()) {
-        let mut elves_cal = Vec::new();
-        let mut total_cal_elf = 0;
-        for (line_index, line) in lines.enumerate() {
-            if let Ok(l) = line {
-                if l == "" {
-                    // empty line -> next elf
-                    let id = elves_cal.len();
-                    println!("Added elf(id={id}) with {total_cal_elf} calories");
-                    elves_cal.push((total_cal_elf, id));
-                    total_cal_elf = 0;
-                } else {
-                    if let Ok(cal) = l.parse::<i32>() {
-                        total_cal_elf = total_cal_elf + cal;
-                    } else {
-                        panic!("unable to parse string to int a line {line_index}")
-                    }
-                }
+    // The input is streamed, so huge calorie logs don't have to fit in memory.
+    let top = args.top as usize;
+    let mut top_elves = TopElves::new(top);
+    let mut failed = false;
+    for (path, name) in paths.iter().zip(&names) {
+        let file = File::open(path).unwrap_or_else(|e| {
+            eprintln!("{name}: {e}");
+            std::process::exit(1);
+        });
+        if let Err(errors) = top_elves.read(name, BufReader::new(file)) {
+            for e in &errors {
+                eprintln!("{}", format_error_in(name, e));
             }
+            failed = true;
         }
+    }
+    if failed {
+        std::process::exit(1);
+    }
+    let top_elves = top_elves.finish().unwrap_or_else(|e| {
+        eprintln!("{}: {e}", names.join(", "));
+        std::process::exit(1);
+    });
 
-        let (max_val, max_id) = (&elves_cal)
-            .iter()
-            .max_by_key(|(val, _index)| val)
-            .expect("No elfs found in the file");
-        println!("The elf with the most calories: id={max_id} with {max_val} calories");
-
-        elves_cal.sort_by_key(|(val, _index)| -val.clone());
-        let top_3_elves = elves_cal.iter().take(3);
-
-        // This somehow ignores the end of line, not sure why.
-        let top3_string_elves = top_3_elves.clone()
-            .map(|(val, index)| format!("elf val={val} index={index}"))
-            .fold(String::new(), |elve, message: String| {
-                message + " " + elve.borrow() + " \r\n"
-            });
-
-        print!("top 3 elves: \n\r {top3_string_elves}");
-
-        let top3_cals = top_3_elves.fold(0, |acc, (val, index_)| acc + val);
-        println!("The top 3 cals={top3_cals}");
-    } else {
-        // TODO provide a proper error here.
-        panic!("Unable to read file");
+    let best = &top_elves[0];
+    println!(
+        "The elf with the most calories: id={} with {} calories",
+        best.id, best.calories
+    );
+
+    println!("top {top} elves:");
+    for elf in &top_elves {
+        println!(
+            " elf val={} index={} from {}:{}-{}",
+            elf.calories, elf.id, names[elf.source], elf.first_line, elf.last_line
+        );
     }
+
+    let total = top_sum(top_elves.iter().map(|elf| elf.calories));
+    println!("The top {top} cals={total}");
 }
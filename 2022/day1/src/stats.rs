@@ -0,0 +1,171 @@
+use std::fmt;
+
+const PERCENTILES: [u32; 5] = [10, 25, 75, 90, 99];
+const HISTOGRAM_WIDTH: usize = 50;
+
+// Distribution of the calories carried by the elves.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats {
+    pub elves: usize,
+    pub min: u64,
+    pub max: u64,
+    pub mean: f64,
+    pub median: f64,
+    pub std_dev: f64,
+    // (percentile, calories) with the nearest-rank method
+    pub percentiles: Vec<(u32, u64)>,
+    pub items: ItemStats,
+    // (lower bound, number of elves) of every bucket
+    pub histogram: Vec<(u64, usize)>,
+}
+
+// Number of food items every elf carries.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ItemStats {
+    pub total: usize,
+    pub min: usize,
+    pub max: usize,
+    pub mean: f64,
+}
+
+fn percentile(sorted: &[u64], p: u32) -> u64 {
+    let rank = (p as usize * sorted.len()).div_ceil(100).max(1);
+    sorted[rank - 1]
+}
+
+fn histogram(sorted: &[u64], buckets: usize) -> Vec<(u64, usize)> {
+    let (min, max) = (sorted[0], sorted[sorted.len() - 1]);
+    // All buckets have the same width, the last one includes the max.
+    let width = ((max - min) / buckets as u64).saturating_add(1);
+    let mut counts = vec![0; buckets];
+    for val in sorted {
+        let bucket = ((val - min) / width) as usize;
+        counts[bucket.min(buckets - 1)] += 1;
+    }
+
+    counts
+        .into_iter()
+        .enumerate()
+        .map(|(i, count)| (min.saturating_add((i as u64).saturating_mul(width)), count))
+        .collect()
+}
+
+impl Stats {
+    // The item counts are in the same order as the elves, returns None without elves.
+    pub fn new(elves_cal: &[(u64, usize)], item_counts: &[usize], buckets: usize) -> Option<Stats> {
+        if elves_cal.is_empty() || buckets == 0 {
+            return None;
+        }
+
+        let mut sorted: Vec<u64> = elves_cal.iter().map(|(val, _)| *val).collect();
+        sorted.sort_unstable();
+
+        let n = sorted.len();
+        // f64 has a smaller precision than u64, but it does not overflow.
+        let mean = sorted.iter().map(|v| *v as f64).sum::<f64>() / n as f64;
+        let median = if n.is_multiple_of(2) {
+            (sorted[n / 2 - 1] as f64 + sorted[n / 2] as f64) / 2.0
+        } else {
+            sorted[n / 2] as f64
+        };
+        let variance = sorted
+            .iter()
+            .map(|v| (*v as f64 - mean).powi(2))
+            .sum::<f64>()
+            / n as f64;
+
+        let items = ItemStats {
+            total: item_counts.iter().sum(),
+            min: item_counts.iter().copied().min().unwrap_or(0),
+            max: item_counts.iter().copied().max().unwrap_or(0),
+            mean: item_counts.iter().sum::<usize>() as f64 / item_counts.len().max(1) as f64,
+        };
+
+        Some(Stats {
+            elves: n,
+            min: sorted[0],
+            max: sorted[n - 1],
+            mean,
+            median,
+            std_dev: variance.sqrt(),
+            percentiles: PERCENTILES
+                .iter()
+                .map(|p| (*p, percentile(&sorted, *p)))
+                .collect(),
+            items,
+            histogram: histogram(&sorted, buckets),
+        })
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "elves:     {}", self.elves)?;
+        writeln!(f, "min:       {}", self.min)?;
+        writeln!(f, "max:       {}", self.max)?;
+        writeln!(f, "mean:      {:.1}", self.mean)?;
+        writeln!(f, "median:    {:.1}", self.median)?;
+        writeln!(f, "std dev:   {:.1}", self.std_dev)?;
+        for (p, val) in &self.percentiles {
+            writeln!(f, "{:<11}{val}", format!("p{p}:"))?;
+        }
+        writeln!(
+            f,
+            "items:     {} in total, {} to {} per elf, {:.1} on average",
+            self.items.total, self.items.min, self.items.max, self.items.mean
+        )?;
+
+        writeln!(f, "histogram:")?;
+        let largest = self.histogram.iter().map(|(_, c)| *c).max().unwrap_or(0);
+        let label_width = self.max.to_string().len();
+        for (lower, count) in &self.histogram {
+            // Scaled to the largest bucket, a bucket with any elves gets at least one '#'.
+            let bar = (count * HISTOGRAM_WIDTH).div_ceil(largest.max(1));
+            writeln!(
+                f,
+                "  {lower:>label_width$} | {:<HISTOGRAM_WIDTH$} {count}",
+                "#".repeat(bar)
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::stats::*;
+
+    #[test]
+    fn test_stats() {
+        let elves: Vec<(u64, usize)> = [6000, 4000, 11000, 24000, 10000]
+            .into_iter()
+            .enumerate()
+            .map(|(id, val)| (val, id))
+            .collect();
+        let stats = Stats::new(&elves, &[3, 1, 2, 3, 1], 4).unwrap();
+
+        assert_eq!((stats.min, stats.max), (4000, 24000));
+        assert_eq!(stats.mean, 11000.0);
+        assert_eq!(stats.median, 10000.0);
+        assert!((stats.std_dev - 6985.7).abs() < 0.1);
+        assert_eq!(stats.percentiles[0], (10, 4000));
+        assert_eq!(stats.percentiles[3], (90, 24000));
+        assert_eq!(stats.items.total, 10);
+        assert_eq!(stats.items.mean, 2.0);
+        assert_eq!(
+            stats.histogram,
+            vec![(4000, 2), (9001, 2), (14002, 0), (19003, 1)]
+        );
+
+        assert!(Stats::new(&[], &[], 4).is_none());
+    }
+
+    #[test]
+    fn test_single_elf() {
+        let stats = Stats::new(&[(5, 0)], &[1], 3).unwrap();
+        assert_eq!(stats.median, 5.0);
+        assert_eq!(stats.std_dev, 0.0);
+        assert_eq!(stats.histogram, vec![(5, 1), (6, 0), (7, 0)]);
+    }
+}
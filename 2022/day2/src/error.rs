@@ -0,0 +1,245 @@
+use std::{error, fmt};
+
+use aoc_core::LocatedError;
+use aoc_input::Position;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    WrongSymbolCount {
+        position: Position,
+        found: String,
+    },
+    InvalidSymbol {
+        position: Position,
+        found: String,
+        expected: String,
+    },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::WrongSymbolCount { found, .. } => {
+                write!(
+                    f,
+                    "expected 2 symbols separated by a space, found '{found}'"
+                )
+            }
+            ParseError::InvalidSymbol {
+                found, expected, ..
+            } => write!(f, "expected {expected}, found '{found}'"),
+        }
+    }
+}
+
+impl error::Error for ParseError {}
+
+impl LocatedError for ParseError {
+    fn position(&self) -> Option<Position> {
+        match self {
+            ParseError::WrongSymbolCount { position, .. } => Some(*position),
+            ParseError::InvalidSymbol { position, .. } => Some(*position),
+        }
+    }
+}
+
+// Errors in a symbol mapping given on the command line, like "X=Rock,Y=Paper,Z=Scissors".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MappingError {
+    InvalidEntry {
+        found: String,
+    },
+    DuplicateSymbol {
+        found: String,
+    },
+    UnknownName {
+        found: String,
+        expected: &'static str,
+    },
+    MappedTwice {
+        name: String,
+    },
+    Unmapped {
+        name: String,
+    },
+    TooManyMoves {
+        found: usize,
+        max: usize,
+    },
+}
+
+impl fmt::Display for MappingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MappingError::InvalidEntry { found } => {
+                write!(f, "expected SYMBOL=NAME, found '{found}'")
+            }
+            MappingError::DuplicateSymbol { found } => {
+                write!(f, "expected every symbol once, found '{found}' again")
+            }
+            MappingError::UnknownName { found, expected } => {
+                write!(f, "expected {expected}, found '{found}'")
+            }
+            MappingError::MappedTwice { name } => {
+                write!(f, "expected one symbol for '{name}', found more")
+            }
+            MappingError::Unmapped { name } => {
+                write!(f, "expected a symbol for '{name}', found none")
+            }
+            MappingError::TooManyMoves { found, max } => {
+                write!(
+                    f,
+                    "expected at most {max} moves to try all mappings, found {found}"
+                )
+            }
+        }
+    }
+}
+
+impl error::Error for MappingError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StrategyError {
+    InvalidLimit { found: String },
+    UnknownMove { found: String },
+    Infeasible,
+}
+
+impl fmt::Display for StrategyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StrategyError::InvalidLimit { found } => {
+                write!(
+                    f,
+                    "expected MOVE=COUNT for every move once, found '{found}'"
+                )
+            }
+            StrategyError::UnknownMove { found } => {
+                write!(f, "expected one of the moves, found '{found}'")
+            }
+            StrategyError::Infeasible => {
+                write!(
+                    f,
+                    "expected a strategy that meets the constraints, found none"
+                )
+            }
+        }
+    }
+}
+
+impl error::Error for StrategyError {}
+
+// Errors in a rules file, the ones without a position are about the rules as a whole.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RulesError {
+    UnknownKeyword {
+        position: Position,
+        found: String,
+    },
+    WrongArgumentCount {
+        position: Position,
+        keyword: &'static str,
+        expected: usize,
+        found: usize,
+    },
+    InvalidPoints {
+        position: Position,
+        found: String,
+    },
+    Duplicate {
+        position: Position,
+        found: String,
+    },
+    UnknownMove {
+        position: Position,
+        found: String,
+    },
+    UnknownOutcome {
+        position: Position,
+        found: String,
+    },
+    BeatsItself {
+        position: Position,
+        found: String,
+    },
+    Contradiction {
+        position: Position,
+        winner: String,
+        loser: String,
+    },
+    NoMoves,
+    MissingOutcome {
+        outcome: &'static str,
+    },
+    Unreachable {
+        opponent: String,
+        outcome: &'static str,
+    },
+}
+
+impl fmt::Display for RulesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RulesError::UnknownKeyword { found, .. } => {
+                write!(f, "expected move, beats or outcome, found '{found}'")
+            }
+            RulesError::WrongArgumentCount {
+                keyword,
+                expected,
+                found,
+                ..
+            } => write!(
+                f,
+                "expected {expected} values after '{keyword}', found {found}"
+            ),
+            RulesError::InvalidPoints { found, .. } => {
+                write!(f, "expected a number of points, found '{found}'")
+            }
+            RulesError::Duplicate { found, .. } => {
+                write!(f, "expected a new name or symbol, found '{found}' again")
+            }
+            RulesError::UnknownMove { found, .. } => {
+                write!(f, "expected one of the moves, found '{found}'")
+            }
+            RulesError::UnknownOutcome { found, .. } => {
+                write!(f, "expected lose, draw or win, found '{found}'")
+            }
+            RulesError::BeatsItself { found, .. } => {
+                write!(f, "expected two different moves, found '{found}' twice")
+            }
+            RulesError::Contradiction { winner, loser, .. } => {
+                write!(f, "expected '{winner}' to lose against '{loser}', found that '{loser}' already beats '{winner}'")
+            }
+            RulesError::NoMoves => write!(f, "expected at least one move, found none"),
+            RulesError::MissingOutcome { outcome } => {
+                write!(f, "expected the points of a {outcome}, found none")
+            }
+            RulesError::Unreachable { opponent, outcome } => {
+                write!(
+                    f,
+                    "expected a move to {outcome} against '{opponent}', found none"
+                )
+            }
+        }
+    }
+}
+
+impl error::Error for RulesError {}
+
+impl LocatedError for RulesError {
+    fn position(&self) -> Option<Position> {
+        match self {
+            RulesError::UnknownKeyword { position, .. }
+            | RulesError::WrongArgumentCount { position, .. }
+            | RulesError::InvalidPoints { position, .. }
+            | RulesError::Duplicate { position, .. }
+            | RulesError::UnknownMove { position, .. }
+            | RulesError::UnknownOutcome { position, .. }
+            | RulesError::BeatsItself { position, .. }
+            | RulesError::Contradiction { position, .. } => Some(*position),
+            RulesError::NoMoves
+            | RulesError::MissingOutcome { .. }
+            | RulesError::Unreachable { .. } => None,
+        }
+    }
+}
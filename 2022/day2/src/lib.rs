@@ -0,0 +1,219 @@
+use aoc_core::{Answer, Solution};
+use aoc_input::{Input, Line};
+
+mod error;
+mod mapping;
+mod play;
+mod report;
+mod rules;
+mod simplex;
+mod strategy;
+
+pub use error::{MappingError, ParseError, RulesError, StrategyError};
+pub use mapping::{mapping_scores, Column, MappingScore};
+pub use play::play;
+pub use report::{Play, Report, RoundReport, Tally};
+pub use rules::{GameResult, Move, Rules};
+pub use strategy::{optimal_strategy, Constraints, Strategy};
+
+// One line of the strategy guide, the second column is a move in part1
+// and the wanted result in part2.
+#[derive(Debug, Clone, Copy)]
+pub struct Round {
+    pub opponent: Move,
+    pub me: Move,
+    // None when the second column is not a result symbol, only part2 needs it
+    pub wanted_result: Option<GameResult>,
+}
+
+impl Round {
+    // Panics when the second column is not a result, see Guide::check_part2.
+    pub fn wanted_result(&self) -> GameResult {
+        self.wanted_result.expect("checked by check_part2")
+    }
+}
+
+// The round and the error for part2 when the second column is not a result.
+fn parse_round(rules: &Rules, line: Line) -> Result<(Round, Option<ParseError>), ParseError> {
+    let unmapped_symbols: Vec<&str> = line.text.split(' ').collect();
+
+    if unmapped_symbols.len() != 2 {
+        return Err(ParseError::WrongSymbolCount {
+            position: line.start(),
+            found: line.text.to_owned(),
+        });
+    }
+
+    let invalid_symbol = |symbol: &str, expected| ParseError::InvalidSymbol {
+        position: line.position_of(symbol),
+        found: symbol.to_owned(),
+        expected,
+    };
+
+    let opponent = rules
+        .opponent_move(unmapped_symbols[0])
+        .ok_or_else(|| invalid_symbol(unmapped_symbols[0], rules.opponent_symbols()))?;
+    let me = rules
+        .my_move(unmapped_symbols[1])
+        .ok_or_else(|| invalid_symbol(unmapped_symbols[1], rules.my_symbols()))?;
+    let wanted_result = rules.result(unmapped_symbols[1]);
+    let result_error = match wanted_result {
+        Some(_) => None,
+        None => Some(invalid_symbol(unmapped_symbols[1], rules.result_symbols())),
+    };
+
+    Ok((
+        Round {
+            opponent,
+            me,
+            wanted_result,
+        },
+        result_error,
+    ))
+}
+
+// The rounds of the strategy guide together with the rules they are played with.
+#[derive(Debug, Clone)]
+pub struct Guide {
+    pub rules: Rules,
+    pub rounds: Vec<Round>,
+    // the second column symbols that are not results, reported by check_part2
+    result_errors: Vec<ParseError>,
+}
+
+impl Guide {
+    pub fn parse(rules: Rules, input: &Input) -> Result<Guide, Vec<ParseError>> {
+        let mut rounds = Vec::new();
+        let mut result_errors = Vec::new();
+        let mut errors = Vec::new();
+        for line in input.lines() {
+            match parse_round(&rules, line) {
+                Ok((round, result_error)) => {
+                    rounds.push(round);
+                    result_errors.extend(result_error);
+                }
+                Err(e) => errors.push(e),
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(Guide {
+                rules,
+                rounds,
+                result_errors,
+            })
+        } else {
+            Err(errors)
+        }
+    }
+
+    // Part2 reads the second column as results, when the moves are mapped to other
+    // symbols that only works if the results are mapped as well.
+    pub fn check_part2(&self) -> Result<(), Vec<ParseError>> {
+        if self.result_errors.is_empty() {
+            Ok(())
+        } else {
+            Err(self.result_errors.clone())
+        }
+    }
+
+    // The second column is the move to play.
+    pub fn total_score_part1(&self) -> i32 {
+        self.rounds
+            .iter()
+            .map(|r| self.rules.score(r.opponent, r.me))
+            .sum()
+    }
+
+    // The second column is the result the round should have, panics when
+    // check_part2 fails.
+    pub fn total_score_part2(&self) -> i32 {
+        self.rounds
+            .iter()
+            .map(|r| {
+                let wanted_move = self.rules.find_move(r.opponent, r.wanted_result());
+                self.rules.score(r.opponent, wanted_move)
+            })
+            .sum()
+    }
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Guide;
+    type Error = ParseError;
+
+    fn parse(input: &Input) -> Result<Guide, Vec<ParseError>> {
+        let guide = Guide::parse(Rules::standard(), input)?;
+        guide.check_part2()?;
+        Ok(guide)
+    }
+
+    fn part1(guide: &Guide) -> Answer {
+        Answer::from(guide.total_score_part1())
+    }
+
+    fn part2(guide: &Guide) -> Answer {
+        Answer::from(guide.total_score_part2())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use std::path::Path;
+
+    #[test]
+    fn test_rpsls_guide() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("rules")
+            .join("rock-paper-scissors-lizard-spock.txt");
+        let rules = Rules::parse(&Input::from_file(&path).unwrap()).unwrap();
+        let guide = Guide::parse(rules, &Input::from_string("test", "A Y\nD Z\nE V\n")).unwrap();
+
+        // lizard loses against rock, spock against lizard and rock against spock
+        assert_eq!(guide.total_score_part1(), 4 + 5 + 1);
+        // paper beats rock, rock beats lizard and rock loses against spock, the first
+        // move in the rules is picked when there are several
+        assert_eq!(guide.total_score_part2(), (2 + 6) + (1 + 6) + 1);
+    }
+
+    #[test]
+    fn test_moves_without_results() {
+        let mut rules = Rules::standard();
+        rules
+            .map_moves(Column::Me, "P=Rock,Q=Paper,R=Scissors")
+            .unwrap();
+        let guide = Guide::parse(rules, &Input::from_string("test", "A Q\nB P\n")).unwrap();
+
+        // part1 only needs the moves
+        assert_eq!(guide.total_score_part1(), 8 + 1);
+        assert!(mapping_scores(&guide).is_ok());
+        let errors = guide.check_part2().unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].to_string(), "expected X, Y or Z, found 'Q'");
+    }
+
+    #[test]
+    fn test_invalid_symbol() {
+        let errors = Day2::parse(&Input::from_string("test", "A Y\nD X\n")).unwrap_err();
+        assert_eq!(errors[0].to_string(), "expected A, B or C, found 'D'");
+    }
+
+    #[test]
+    fn test_example_part1() {
+        assert_eq!(
+            Day2::part1(&aoc_core::example::<Day2>(env!("CARGO_MANIFEST_DIR"))),
+            Answer::from(15)
+        );
+    }
+
+    #[test]
+    fn test_example_part2() {
+        assert_eq!(
+            Day2::part2(&aoc_core::example::<Day2>(env!("CARGO_MANIFEST_DIR"))),
+            Answer::from(12)
+        );
+    }
+}
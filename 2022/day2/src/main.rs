@@ -1,117 +1,168 @@
-use std::{
-    fs::File,
-    io::{self, BufRead, BufReader, Lines},
+use std::path::PathBuf;
+
+use aoc_core::{format_error, init_logging, read_or_exit, LocatedError};
+use aoc_input::Input;
+use clap::{ArgGroup, Parser, ValueEnum};
+use day2::{
+    mapping_scores, optimal_strategy, play, Column, Constraints, Guide, Move, Report, Rules,
 };
-#[derive(Debug, Clone, Copy)]
-enum Move {
-    Rock,
-    Paper,
-    Scissors,
-}
 
-fn read_lines(filename: &std::path::Path) -> io::Result<Lines<BufReader<File>>> {
-    let file = File::open(filename)?;
-    let reader = BufReader::new(file);
-    let lines = reader.lines();
-    Ok(lines)
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum ReportFormat {
+    Text,
+    Csv,
 }
 
-fn map_symbol(symbol: &str, line_number: usize) -> Move {
-    match symbol {
-        "A" => Move::Rock,
-        "B" => Move::Paper,
-        "C" => Move::Scissors,
-        "X" => Move::Rock,
-        "Y" => Move::Paper,
-        "Z" => Move::Scissors,
-        _ => panic!("enable to map symbol='{symbol}' at line={line_number}"),
-    }
+// Only one of the ways to use the guide can be asked for at a time.
+#[derive(Parser, Debug)]
+#[command(group(ArgGroup::new("mode").args(["all_mappings", "optimize", "report", "play"])))]
+struct Args {
+    #[arg(short, long, default_value = "./input.txt")]
+    input: PathBuf,
+
+    /// Rules of the game, see rules/ for the format, defaults to rock paper scissors
+    #[arg(short, long)]
+    rules: Option<PathBuf>,
+
+    /// What the opponent symbols mean, like "A=Rock,B=Paper,C=Scissors"
+    #[arg(long)]
+    opponent: Option<String>,
+
+    /// What the second column means in part1, like "X=Rock,Y=Paper,Z=Scissors"
+    #[arg(long)]
+    me: Option<String>,
+
+    /// What the second column means in part2, like "X=lose,Y=draw,Z=win"
+    #[arg(long)]
+    results: Option<String>,
+
+    /// Score part1 for every possible meaning of the second column
+    #[arg(long)]
+    all_mappings: bool,
+
+    /// Print the guide with the highest score against the opponent column
+    #[arg(long)]
+    optimize: bool,
+
+    /// With --optimize, at least this many rounds have to be lost
+    #[arg(long, default_value_t = 0, requires = "optimize")]
+    min_losses: usize,
+
+    /// With --optimize, the most times a move can be played, like "Rock=10,Paper=5"
+    #[arg(long, requires = "optimize")]
+    max_uses: Option<String>,
+
+    /// Print every round with the points of both parts, as a table or as csv
+    #[arg(long, value_enum, num_args = 0..=1, default_missing_value = "text")]
+    report: Option<ReportFormat>,
+
+    /// Play the rounds yourself against the opponent moves of the guide
+    #[arg(long)]
+    play: bool,
 }
 
-fn map_result(symbol: &str, line_number: usize) -> GameResult {
-    match symbol {
-        "X" => GameResult::Lose,
-        "Y" => GameResult::Draw,
-        "Z" => GameResult::Win,
-        _ => panic!("enable to map gameresult='{symbol}' at line={line_number}"),
-    }
+fn exit_on_errors<T, E: LocatedError>(input: &Input, result: Result<T, Vec<E>>) -> T {
+    result.unwrap_or_else(|errors| {
+        for e in &errors {
+            eprintln!("{}", format_error(input, e));
+        }
+        std::process::exit(1);
+    })
 }
 
-fn point_symbol(me: Move) -> i32 {
-    return match me {
-        Move::Rock => 1,
-        Move::Paper => 2,
-        Move::Scissors => 3,
-    };
+fn exit_on_error<T, E: std::fmt::Display>(flag: &str, result: Result<T, E>) -> T {
+    result.unwrap_or_else(|e| {
+        eprintln!("{flag}: {e}");
+        std::process::exit(1);
+    })
 }
 
-fn score(opponent: Move, me: Move) -> i32 {
-    let points_move_choice = point_symbol(me);
-    let match_points = match (opponent, me) {
-        (Move::Rock, Move::Paper) => 6,     // win
-        (Move::Paper, Move::Scissors) => 6, // win
-        (Move::Scissors, Move::Rock) => 6,  // win
-        (Move::Paper, Move::Rock) => 0,     // lose
-        (Move::Scissors, Move::Paper) => 0, // lose
-        (Move::Rock, Move::Scissors) => 0,  // lose
-        _ => 3,                             // draw
+fn main() {
+    init_logging(0);
+    let args = Args::parse();
+
+    let mut rules = match &args.rules {
+        Some(path) => {
+            let input = read_or_exit(path);
+            exit_on_errors(&input, Rules::parse(&input))
+        }
+        None => Rules::standard(),
     };
 
-    return points_move_choice + match_points;
-}
+    if let Some(spec) = &args.opponent {
+        exit_on_error("--opponent", rules.map_moves(Column::Opponent, spec));
+    }
+    if let Some(spec) = &args.me {
+        exit_on_error("--me", rules.map_moves(Column::Me, spec));
+    }
+    if let Some(spec) = &args.results {
+        exit_on_error("--results", rules.map_results(spec));
+    }
 
-enum GameResult {
-    Win,
-    Draw,
-    Lose
-}
+    let input = read_or_exit(&args.input);
+    let guide = exit_on_errors(&input, Guide::parse(rules, &input));
+
+    if args.optimize {
+        let mut constraints = Constraints {
+            min_losses: args.min_losses,
+            max_uses: Vec::new(),
+        };
+        if let Some(spec) = &args.max_uses {
+            constraints.max_uses = exit_on_error(
+                "--max-uses",
+                Constraints::parse_max_uses(&guide.rules, spec),
+            );
+        }
 
-fn find_move(opponent: Move, wanted_result: GameResult) -> Move
-{
-    return match (opponent, wanted_result) {
-        (Move::Rock, GameResult::Win) => Move::Paper,
-        (Move::Rock, GameResult::Lose) => Move::Scissors,
-        (Move::Paper, GameResult::Win) => Move::Scissors,
-        (Move::Paper, GameResult::Lose) => Move::Rock,
-        (Move::Scissors, GameResult::Win) => Move::Rock,
-        (Move::Scissors, GameResult::Lose) => Move::Paper,
-        _ => opponent // draw
+        let opponent: Vec<Move> = guide.rounds.iter().map(|r| r.opponent).collect();
+        let strategy = exit_on_error(
+            "--optimize",
+            optimal_strategy(&guide.rules, &opponent, &constraints),
+        );
+        println!("optimal score = {}", strategy.score);
+        for (opponent, me) in opponent.iter().zip(&strategy.moves) {
+            println!(
+                "{} {}",
+                guide.rules.opponent_symbol(*opponent),
+                guide.rules.my_symbol(*me)
+            );
+        }
+        return;
     }
-}
 
-fn main() {
-    let p = std::path::Path::new("./input.txt");
-
-    let mut total_score_part1 = 0;
-    let mut total_score_part2 = 0;
-    for (line_index, maybe_line) in read_lines(p).expect("unable to read file").enumerate() {
-        if let Ok(line) = maybe_line {
-            // -> parse line
-            let unmapped_symbols: Vec<&str> = line
-                .split(" ")
-                .collect();
-
-            if unmapped_symbols.len() != 2 {
-                panic!("line={line_index} does not contain 2 valid symbols.");
-            }
-
-            // Very ugly side effect in map symbol, could be better using result.
-            let opponent_move = map_symbol(unmapped_symbols[0], line_index);
-            let me_move = map_symbol(unmapped_symbols[1], line_index);
-
-            let score_part1 = score(opponent_move, me_move);
-            total_score_part1 = total_score_part1 + score_part1;
-        
-            // part2
-            let wanted_result_part2 = map_result(unmapped_symbols[1], line_index);
-            let wanted_move = find_move(opponent_move, wanted_result_part2);
-            let score_part2 = score(opponent_move, wanted_move);
-            total_score_part2 = total_score_part2 + score_part2;
-        } else {
-            panic!("unable to read line at {line_index}");
+    if args.all_mappings {
+        let mut scores = mapping_scores(&guide).unwrap_or_else(|e| {
+            eprintln!("{e}");
+            std::process::exit(1);
+        });
+        scores.sort_by_key(|s| -s.score);
+        for s in &scores {
+            println!("{}: part1 score = {}", s.describe(&guide.rules), s.score);
+        }
+        return;
+    }
+
+    // everything below plays part2 as well, that needs results in the second column
+    if args.play {
+        exit_on_errors(&input, guide.check_part2());
+        play(&guide, std::io::stdin().lock(), std::io::stdout().lock())
+            .unwrap_or_else(|e| panic!("{e}"));
+        return;
+    }
+
+    if let Some(format) = args.report {
+        exit_on_errors(&input, guide.check_part2());
+        let report = Report::new(&guide);
+        match format {
+            ReportFormat::Text => print!("{report}"),
+            ReportFormat::Csv => report
+                .write_csv(std::io::stdout().lock())
+                .unwrap_or_else(|e| panic!("{e}")),
         }
+        return;
     }
 
-    print!("part1 score = {total_score_part1} -> should be 11841");
-    print!("part2 score = {total_score_part2}'");
+    println!("part1 score = {}", guide.total_score_part1());
+    exit_on_errors(&input, guide.check_part2());
+    println!("part2 score = {}", guide.total_score_part2());
 }
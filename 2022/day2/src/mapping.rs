@@ -0,0 +1,238 @@
+use crate::error::MappingError;
+use crate::rules::{GameResult, Move, Rules};
+use crate::Guide;
+
+// Trying every mapping of n moves takes n! games, more than this is too slow to be useful.
+const MAX_PERMUTED_MOVES: usize = 8;
+
+// Which column of the strategy guide a move mapping is for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Column {
+    Opponent,
+    Me,
+}
+
+// "X=Rock,Y=Paper,Z=Scissors", spaces around the symbols and names are allowed.
+fn parse_spec(spec: &str) -> Result<Vec<(&str, &str)>, MappingError> {
+    let mut entries: Vec<(&str, &str)> = Vec::new();
+    for entry in spec.split(',') {
+        let (symbol, name) = match entry.split_once('=') {
+            Some((symbol, name)) if !symbol.trim().is_empty() && !name.trim().is_empty() => {
+                (symbol.trim(), name.trim())
+            }
+            _ => {
+                return Err(MappingError::InvalidEntry {
+                    found: entry.to_owned(),
+                })
+            }
+        };
+        if entries.iter().any(|(s, _)| *s == symbol) {
+            return Err(MappingError::DuplicateSymbol {
+                found: symbol.to_owned(),
+            });
+        }
+        entries.push((symbol, name));
+    }
+
+    Ok(entries)
+}
+
+impl Rules {
+    // Every move needs exactly one symbol, the names are matched without case.
+    pub fn map_moves(&mut self, column: Column, spec: &str) -> Result<(), MappingError> {
+        let mut symbols: Vec<Option<String>> = vec![None; self.moves().count()];
+        for (symbol, name) in parse_spec(spec)? {
+            let m = self
+                .moves()
+                .find(|m| self.name(*m).eq_ignore_ascii_case(name))
+                .ok_or_else(|| MappingError::UnknownName {
+                    found: name.to_owned(),
+                    expected: "a move",
+                })?;
+            if symbols[m.index()].is_some() {
+                return Err(MappingError::MappedTwice {
+                    name: self.name(m).to_owned(),
+                });
+            }
+            symbols[m.index()] = Some(symbol.to_owned());
+        }
+
+        let symbols = self
+            .moves()
+            .zip(symbols)
+            .map(|(m, symbol)| {
+                symbol.ok_or_else(|| MappingError::Unmapped {
+                    name: self.name(m).to_owned(),
+                })
+            })
+            .collect::<Result<Vec<String>, MappingError>>()?;
+        self.set_symbols(column == Column::Opponent, symbols);
+        Ok(())
+    }
+
+    // Every outcome needs at least one symbol, like "X=lose,Y=draw,Z=win".
+    pub fn map_results(&mut self, spec: &str) -> Result<(), MappingError> {
+        let mut symbols: [Vec<String>; 3] = Default::default();
+        for (symbol, name) in parse_spec(spec)? {
+            let result = GameResult::from_name(&name.to_ascii_lowercase()).ok_or_else(|| {
+                MappingError::UnknownName {
+                    found: name.to_owned(),
+                    expected: "lose, draw or win",
+                }
+            })?;
+            symbols[result.index()].push(symbol.to_owned());
+        }
+
+        for result in GameResult::ALL {
+            if symbols[result.index()].is_empty() {
+                return Err(MappingError::Unmapped {
+                    name: result.name().to_owned(),
+                });
+            }
+        }
+        self.set_result_symbols(symbols);
+        Ok(())
+    }
+}
+
+// The score of part1 when the symbol of moves[i] in the rules means moves[i] instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MappingScore {
+    pub moves: Vec<Move>,
+    pub score: i32,
+}
+
+impl MappingScore {
+    // "X=Rock Y=Paper Z=Scissors"
+    pub fn describe(&self, rules: &Rules) -> String {
+        rules
+            .moves()
+            .zip(&self.moves)
+            .map(|(symbol_of, m)| format!("{}={}", rules.my_symbol(symbol_of), rules.name(*m)))
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+}
+
+// All the orderings of the items, in lexicographic order of their positions.
+fn permutations<T: Copy>(items: &[T]) -> Vec<Vec<T>> {
+    if items.len() <= 1 {
+        return vec![items.to_vec()];
+    }
+
+    let mut result = Vec::new();
+    for (i, first) in items.iter().enumerate() {
+        let mut rest = items.to_vec();
+        rest.remove(i);
+        for mut permutation in permutations(&rest) {
+            permutation.insert(0, *first);
+            result.push(permutation);
+        }
+    }
+    result
+}
+
+// Scores part1 for every way the second column can be read as moves, the mapping
+// of the rules comes first.
+pub fn mapping_scores(guide: &Guide) -> Result<Vec<MappingScore>, MappingError> {
+    let moves: Vec<Move> = guide.rules.moves().collect();
+    if moves.len() > MAX_PERMUTED_MOVES {
+        return Err(MappingError::TooManyMoves {
+            found: moves.len(),
+            max: MAX_PERMUTED_MOVES,
+        });
+    }
+
+    let scores = permutations(&moves)
+        .into_iter()
+        .map(|mapping| {
+            let score = guide
+                .rounds
+                .iter()
+                .map(|r| guide.rules.score(r.opponent, mapping[r.me.index()]))
+                .sum();
+            MappingScore {
+                moves: mapping,
+                score,
+            }
+        })
+        .collect();
+    Ok(scores)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mapping::*;
+    use aoc_input::Input;
+
+    #[test]
+    fn test_map_moves() {
+        let mut rules = Rules::standard();
+        rules
+            .map_moves(Column::Me, "X=scissors, Y=Rock, Z=Paper")
+            .unwrap();
+        assert_eq!(rules.name(rules.my_move("X").unwrap()), "Scissors");
+        assert_eq!(rules.my_symbols(), "Y, Z or X");
+
+        let mut rules = Rules::standard();
+        assert_eq!(
+            rules.map_moves(Column::Opponent, "A=Rock,B=Paper"),
+            Err(MappingError::Unmapped {
+                name: "Scissors".to_owned()
+            })
+        );
+        assert_eq!(
+            rules.map_moves(Column::Opponent, "A=Rock,B=Rock,C=Paper"),
+            Err(MappingError::MappedTwice {
+                name: "Rock".to_owned()
+            })
+        );
+        assert_eq!(
+            rules.map_moves(Column::Opponent, "A=Rock,A=Paper,C=Scissors"),
+            Err(MappingError::DuplicateSymbol {
+                found: "A".to_owned()
+            })
+        );
+        assert_eq!(
+            rules.map_moves(Column::Opponent, "A:Rock"),
+            Err(MappingError::InvalidEntry {
+                found: "A:Rock".to_owned()
+            })
+        );
+    }
+
+    #[test]
+    fn test_map_results() {
+        let mut rules = Rules::standard();
+        rules.map_results("X=win,Y=lose,Z=draw").unwrap();
+        assert_eq!(rules.result("X"), Some(GameResult::Win));
+
+        assert_eq!(
+            rules.map_results("X=win,Y=lose,Z=tie"),
+            Err(MappingError::UnknownName {
+                found: "tie".to_owned(),
+                expected: "lose, draw or win"
+            })
+        );
+    }
+
+    #[test]
+    fn test_mapping_scores() {
+        let input = Input::from_string("test", "A Y\nB X\nC Z\n");
+        let guide = Guide::parse(Rules::standard(), &input).unwrap();
+        let scores = mapping_scores(&guide).unwrap();
+
+        assert_eq!(scores.len(), 6);
+        assert_eq!(scores[0].score, 15);
+        assert_eq!(
+            scores[0].describe(&guide.rules),
+            "X=Rock Y=Paper Z=Scissors"
+        );
+        // Y=Rock X=Paper Z=Scissors draws every round
+        let swapped = scores
+            .iter()
+            .find(|s| s.describe(&guide.rules) == "X=Paper Y=Rock Z=Scissors")
+            .unwrap();
+        assert_eq!(swapped.score, (1 + 3) + (2 + 3) + (3 + 3));
+    }
+}
@@ -0,0 +1,116 @@
+use std::io::{self, BufRead, Write};
+
+use crate::report::Report;
+use crate::rules::{Move, Rules};
+use crate::Guide;
+
+// The symbol of the second column or the name of the move, without case.
+fn read_move(rules: &Rules, answer: &str) -> Option<Move> {
+    rules.my_move(answer).or_else(|| {
+        rules
+            .moves()
+            .find(|m| rules.name(*m).eq_ignore_ascii_case(answer))
+    })
+}
+
+// Plays the rounds of the guide with the moves read from input, the opponent
+// plays what the guide says. Stops after the last round, at the end of input
+// or on "quit", and returns the score together with the number of rounds played.
+pub fn play(
+    guide: &Guide,
+    mut input: impl BufRead,
+    mut out: impl Write,
+) -> io::Result<(i32, usize)> {
+    let rules = &guide.rules;
+    let report = Report::new(guide);
+    let choices = rules
+        .moves()
+        .map(|m| format!("{}={}", rules.my_symbol(m), rules.name(m)))
+        .collect::<Vec<String>>()
+        .join(" ");
+
+    let mut total = 0;
+    let mut played = 0;
+    let mut answer = String::new();
+    'rounds: for (i, round) in report.rounds.iter().enumerate() {
+        let me = loop {
+            write!(
+                out,
+                "round {}: the opponent plays {}, your move ({choices}): ",
+                i + 1,
+                rules.name(round.opponent)
+            )?;
+            out.flush()?;
+
+            answer.clear();
+            if input.read_line(&mut answer)? == 0 {
+                writeln!(out)?;
+                break 'rounds;
+            }
+            let answer = answer.trim();
+            if answer.eq_ignore_ascii_case("quit") {
+                break 'rounds;
+            }
+            match read_move(rules, answer) {
+                Some(me) => break me,
+                None => writeln!(out, "expected one of {choices} or quit, found '{answer}'")?,
+            }
+        };
+
+        let score = rules.score(round.opponent, me);
+        total += score;
+        played += 1;
+        writeln!(
+            out,
+            "{} against {}: {}, +{score}, total {total} (part1 guide {}, part2 guide {})",
+            rules.name(me),
+            rules.name(round.opponent),
+            rules.outcome(round.opponent, me).name(),
+            round.part1.total,
+            round.part2.total
+        )?;
+    }
+
+    let (guide1, guide2) = match played {
+        0 => (0, 0),
+        n => (
+            report.rounds[n - 1].part1.total,
+            report.rounds[n - 1].part2.total,
+        ),
+    };
+    writeln!(
+        out,
+        "{played} rounds played, score = {total}, the guide scores {guide1} in part1 and {guide2} in part2"
+    )?;
+    Ok((total, played))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::play::*;
+    use aoc_input::Input;
+
+    #[test]
+    fn test_play() {
+        let input = Input::from_string("test", "A Y\nB X\nC Z\n");
+        let guide = Guide::parse(Rules::standard(), &input).unwrap();
+
+        let mut out = Vec::new();
+        let moves = "Y\nwell\nscissors\n";
+        assert_eq!(
+            play(&guide, moves.as_bytes(), &mut out).unwrap(),
+            (8 + 9, 2)
+        );
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.contains("expected one of X=Rock Y=Paper Z=Scissors or quit, found 'well'"));
+        assert!(text.ends_with(
+            "2 rounds played, score = 17, the guide scores 9 in part1 and 5 in part2\n"
+        ));
+
+        let mut out = Vec::new();
+        assert_eq!(
+            play(&guide, "X\nquit\nZ\n".as_bytes(), &mut out).unwrap(),
+            (4, 1)
+        );
+    }
+}
@@ -0,0 +1,273 @@
+use std::fmt;
+use std::io::{self, Write};
+
+use serde::Serialize;
+
+use crate::rules::{GameResult, Move};
+use crate::Guide;
+
+// How one round goes when the guide is read one way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Play {
+    pub me: Move,
+    pub result: GameResult,
+    pub shape_points: i32,
+    pub outcome_points: i32,
+    // the score of this and all the rounds before it
+    pub total: i32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RoundReport {
+    pub opponent: Move,
+    pub part1: Play,
+    pub part2: Play,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Tally {
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+}
+
+impl Tally {
+    fn add(&mut self, result: GameResult) {
+        match result {
+            GameResult::Win => self.wins += 1,
+            GameResult::Draw => self.draws += 1,
+            GameResult::Lose => self.losses += 1,
+        }
+    }
+}
+
+// Every round of the guide played both ways, part1 reads the second column as
+// a move and part2 as the wanted result.
+#[derive(Debug, Clone)]
+pub struct Report<'a> {
+    guide: &'a Guide,
+    pub rounds: Vec<RoundReport>,
+    pub part1: Tally,
+    pub part2: Tally,
+}
+
+// One line of the csv export, both parts next to each other.
+#[derive(Serialize)]
+struct CsvRow<'a> {
+    round: usize,
+    opponent: &'a str,
+    part1_move: &'a str,
+    part1_shape: i32,
+    part1_outcome: i32,
+    part1_result: &'static str,
+    part1_total: i32,
+    part2_move: &'a str,
+    part2_shape: i32,
+    part2_outcome: i32,
+    part2_result: &'static str,
+    part2_total: i32,
+}
+
+impl<'a> Report<'a> {
+    // Panics when check_part2 of the guide fails.
+    pub fn new(guide: &'a Guide) -> Report<'a> {
+        let rules = &guide.rules;
+        let play = |opponent: Move, me: Move, previous: i32| {
+            let result = rules.outcome(opponent, me);
+            let shape_points = rules.points(me);
+            let outcome_points = rules.outcome_points(result);
+            Play {
+                me,
+                result,
+                shape_points,
+                outcome_points,
+                total: previous + shape_points + outcome_points,
+            }
+        };
+
+        let mut report = Report {
+            guide,
+            rounds: Vec::with_capacity(guide.rounds.len()),
+            part1: Tally::default(),
+            part2: Tally::default(),
+        };
+        let (mut total1, mut total2) = (0, 0);
+        for r in &guide.rounds {
+            let part1 = play(r.opponent, r.me, total1);
+            let wanted_move = rules.find_move(r.opponent, r.wanted_result());
+            let part2 = play(r.opponent, wanted_move, total2);
+            (total1, total2) = (part1.total, part2.total);
+            report.part1.add(part1.result);
+            report.part2.add(part2.result);
+            report.rounds.push(RoundReport {
+                opponent: r.opponent,
+                part1,
+                part2,
+            });
+        }
+
+        report
+    }
+
+    pub fn write_csv(&self, out: impl Write) -> io::Result<()> {
+        let rules = &self.guide.rules;
+        let mut writer = csv::Writer::from_writer(out);
+        for (i, r) in self.rounds.iter().enumerate() {
+            writer.serialize(CsvRow {
+                round: i + 1,
+                opponent: rules.name(r.opponent),
+                part1_move: rules.name(r.part1.me),
+                part1_shape: r.part1.shape_points,
+                part1_outcome: r.part1.outcome_points,
+                part1_result: r.part1.result.name(),
+                part1_total: r.part1.total,
+                part2_move: rules.name(r.part2.me),
+                part2_shape: r.part2.shape_points,
+                part2_outcome: r.part2.outcome_points,
+                part2_result: r.part2.result.name(),
+                part2_total: r.part2.total,
+            })?;
+        }
+        writer.flush()
+    }
+}
+
+impl fmt::Display for Report<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rules = &self.guide.rules;
+        // wide enough for every move name and the header
+        let name_width = rules
+            .moves()
+            .map(|m| rules.name(m).len())
+            .max()
+            .unwrap_or(0)
+            .max("opponent".len());
+        let play = |p: &Play| {
+            format!(
+                "{:<name_width$} {:>5} {:>7} {:<6} {:>6}",
+                rules.name(p.me),
+                p.shape_points,
+                p.outcome_points,
+                p.result.name(),
+                p.total
+            )
+        };
+        let header = format!(
+            "{:<name_width$} {:>5} {:>7} {:<6} {:>6}",
+            "move", "shape", "outcome", "result", "total"
+        );
+
+        writeln!(
+            f,
+            "{:>5} {:<name_width$} | {:<w$} | part2",
+            "",
+            "",
+            "part1",
+            w = header.len()
+        )?;
+        writeln!(
+            f,
+            "{:>5} {:<name_width$} | {header} | {header}",
+            "round", "opponent"
+        )?;
+        for (i, r) in self.rounds.iter().enumerate() {
+            writeln!(
+                f,
+                "{:>5} {:<name_width$} | {} | {}",
+                i + 1,
+                rules.name(r.opponent),
+                play(&r.part1),
+                play(&r.part2)
+            )?;
+        }
+
+        for (part, tally, score) in [
+            (
+                "part1",
+                self.part1,
+                self.rounds.last().map_or(0, |r| r.part1.total),
+            ),
+            (
+                "part2",
+                self.part2,
+                self.rounds.last().map_or(0, |r| r.part2.total),
+            ),
+        ] {
+            writeln!(
+                f,
+                "{part}: {} wins, {} draws, {} losses, score = {score}",
+                tally.wins, tally.draws, tally.losses
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::report::*;
+    use crate::Rules;
+    use aoc_input::Input;
+
+    fn example() -> Guide {
+        Guide::parse(
+            Rules::standard(),
+            &Input::from_string("test", "A Y\nB X\nC Z\n"),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_report() {
+        let guide = example();
+        let report = Report::new(&guide);
+
+        let last = report.rounds.last().unwrap();
+        assert_eq!(last.part1.total, guide.total_score_part1());
+        assert_eq!(last.part2.total, guide.total_score_part2());
+        assert_eq!(
+            report.part1,
+            Tally {
+                wins: 1,
+                draws: 1,
+                losses: 1
+            }
+        );
+        assert_eq!(
+            report.part2,
+            Tally {
+                wins: 1,
+                draws: 1,
+                losses: 1
+            }
+        );
+
+        // B X: the opponent plays paper, rock loses in part1 and in part2
+        let second = report.rounds[1];
+        assert_eq!(guide.rules.name(second.part1.me), "Rock");
+        assert_eq!(
+            (second.part1.shape_points, second.part1.outcome_points),
+            (1, 0)
+        );
+        assert_eq!(second.part1.total, 8 + 1);
+        assert_eq!(second.part2.total, 4 + 1);
+    }
+
+    #[test]
+    fn test_write_csv() {
+        let guide = example();
+        let mut out = Vec::new();
+        Report::new(&guide).write_csv(&mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+
+        assert_eq!(lines.len(), 4);
+        assert_eq!(
+            lines[0],
+            "round,opponent,part1_move,part1_shape,part1_outcome,part1_result,part1_total,\
+             part2_move,part2_shape,part2_outcome,part2_result,part2_total"
+        );
+        assert_eq!(lines[1], "1,Rock,Paper,2,6,win,8,Rock,1,3,draw,4");
+    }
+}
@@ -0,0 +1,523 @@
+use aoc_input::{Input, Line};
+
+use aoc_core::LocatedError;
+
+use crate::error::RulesError;
+
+const STANDARD_RULES: &str = include_str!("../rules/rock-paper-scissors.txt");
+
+// A move is the index of the move in the rules, in the order they are declared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Move(usize);
+
+impl Move {
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GameResult {
+    Win,
+    Draw,
+    Lose,
+}
+
+impl GameResult {
+    pub const ALL: [GameResult; 3] = [GameResult::Lose, GameResult::Draw, GameResult::Win];
+
+    pub(crate) fn index(self) -> usize {
+        match self {
+            GameResult::Lose => 0,
+            GameResult::Draw => 1,
+            GameResult::Win => 2,
+        }
+    }
+
+    pub(crate) fn from_name(name: &str) -> Option<GameResult> {
+        match name {
+            "lose" => Some(GameResult::Lose),
+            "draw" => Some(GameResult::Draw),
+            "win" => Some(GameResult::Win),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            GameResult::Lose => "lose",
+            GameResult::Draw => "draw",
+            GameResult::Win => "win",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct MoveRule {
+    name: String,
+    points: i32,
+    opponent_symbol: String,
+    symbol: String,
+}
+
+#[derive(Debug, Clone)]
+struct OutcomeRule {
+    points: i32,
+    symbols: Vec<String>,
+}
+
+// The moves of the game, which move beats which and the points of every outcome.
+// See rules/rock-paper-scissors.txt for the format.
+#[derive(Debug, Clone)]
+pub struct Rules {
+    moves: Vec<MoveRule>,
+    // beats[a][b] is true when a beats b
+    beats: Vec<Vec<bool>>,
+    // indexed by GameResult::index
+    outcomes: [OutcomeRule; 3],
+}
+
+// The rules while they are parsed, everything is still optional.
+#[derive(Default)]
+struct RulesBuilder<'a> {
+    moves: Vec<MoveRule>,
+    beats: Vec<(Line<'a>, &'a str, &'a str)>,
+    outcomes: [Option<OutcomeRule>; 3],
+}
+
+fn parse_points(line: &Line, token: &str) -> Result<i32, RulesError> {
+    token.parse().map_err(|_| RulesError::InvalidPoints {
+        position: line.position_of(token),
+        found: token.to_owned(),
+    })
+}
+
+impl<'a> RulesBuilder<'a> {
+    fn add_move(&mut self, line: &Line, args: &[&str]) -> Result<(), RulesError> {
+        let (name, points, opponent_symbol, symbol) = match args {
+            [name, points, opponent_symbol, symbol] => (*name, *points, *opponent_symbol, *symbol),
+            _ => {
+                return Err(RulesError::WrongArgumentCount {
+                    position: line.start(),
+                    keyword: "move",
+                    expected: 4,
+                    found: args.len(),
+                })
+            }
+        };
+
+        let points = parse_points(line, points)?;
+        for m in &self.moves {
+            let duplicate = if m.name == name {
+                Some(name)
+            } else if m.opponent_symbol == opponent_symbol {
+                Some(opponent_symbol)
+            } else if m.symbol == symbol {
+                Some(symbol)
+            } else {
+                None
+            };
+            if let Some(found) = duplicate {
+                return Err(RulesError::Duplicate {
+                    position: line.position_of(found),
+                    found: found.to_owned(),
+                });
+            }
+        }
+
+        self.moves.push(MoveRule {
+            name: name.to_owned(),
+            points,
+            opponent_symbol: opponent_symbol.to_owned(),
+            symbol: symbol.to_owned(),
+        });
+        Ok(())
+    }
+
+    fn add_outcome(&mut self, line: &Line, args: &[&str]) -> Result<(), RulesError> {
+        let (name, points, symbols) = match args {
+            [name, points, symbols @ ..] if !symbols.is_empty() => (*name, *points, symbols),
+            _ => {
+                return Err(RulesError::WrongArgumentCount {
+                    position: line.start(),
+                    keyword: "outcome",
+                    expected: 3,
+                    found: args.len(),
+                })
+            }
+        };
+
+        let result = GameResult::from_name(name).ok_or_else(|| RulesError::UnknownOutcome {
+            position: line.position_of(name),
+            found: name.to_owned(),
+        })?;
+        if self.outcomes[result.index()].is_some() {
+            return Err(RulesError::Duplicate {
+                position: line.position_of(name),
+                found: name.to_owned(),
+            });
+        }
+        let points = parse_points(line, points)?;
+
+        let known_symbols: Vec<&String> = self
+            .outcomes
+            .iter()
+            .flatten()
+            .flat_map(|o| &o.symbols)
+            .collect();
+        for (i, symbol) in symbols.iter().enumerate() {
+            if known_symbols.iter().any(|s| s == symbol) || symbols[..i].contains(symbol) {
+                return Err(RulesError::Duplicate {
+                    position: line.position_of(symbol),
+                    found: symbol.to_string(),
+                });
+            }
+        }
+
+        self.outcomes[result.index()] = Some(OutcomeRule {
+            points,
+            symbols: symbols.iter().map(|s| s.to_string()).collect(),
+        });
+        Ok(())
+    }
+
+    // The relations are checked once all the moves are known, they can be declared in any order.
+    fn build(self) -> Result<Rules, Vec<RulesError>> {
+        let mut errors = Vec::new();
+        let move_count = self.moves.len();
+        let mut beats = vec![vec![false; move_count]; move_count];
+        let find = |name: &str| self.moves.iter().position(|m| m.name == name);
+
+        for (line, winner, loser) in &self.beats {
+            let unknown = |name: &str| RulesError::UnknownMove {
+                position: line.position_of(name),
+                found: name.to_owned(),
+            };
+            let (w, l) = match (find(winner), find(loser)) {
+                (Some(w), Some(l)) => (w, l),
+                (None, _) => {
+                    errors.push(unknown(winner));
+                    continue;
+                }
+                (_, None) => {
+                    errors.push(unknown(loser));
+                    continue;
+                }
+            };
+
+            if w == l {
+                errors.push(RulesError::BeatsItself {
+                    position: line.position_of(loser),
+                    found: loser.to_string(),
+                });
+            } else if beats[l][w] {
+                errors.push(RulesError::Contradiction {
+                    position: line.start(),
+                    winner: winner.to_string(),
+                    loser: loser.to_string(),
+                });
+            } else {
+                beats[w][l] = true;
+            }
+        }
+
+        if self.moves.is_empty() {
+            errors.push(RulesError::NoMoves);
+        }
+        for result in GameResult::ALL {
+            if self.outcomes[result.index()].is_none() {
+                errors.push(RulesError::MissingOutcome {
+                    outcome: result.name(),
+                });
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        let [lose, draw, win] = self.outcomes;
+        let rules = Rules {
+            moves: self.moves,
+            beats,
+            outcomes: [lose.unwrap(), draw.unwrap(), win.unwrap()],
+        };
+
+        // Part2 has to find a move for every outcome against every move of the opponent.
+        for opponent in rules.moves() {
+            for result in GameResult::ALL {
+                if rules.try_find_move(opponent, result).is_none() {
+                    errors.push(RulesError::Unreachable {
+                        opponent: rules.name(opponent).to_owned(),
+                        outcome: result.name(),
+                    });
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(rules)
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+impl Rules {
+    // Every line is checked, all the errors are returned.
+    pub fn parse(input: &Input) -> Result<Rules, Vec<RulesError>> {
+        let mut builder = RulesBuilder::default();
+        let mut errors = Vec::new();
+        for line in input.lines() {
+            let tokens: Vec<&str> = line.text.split_whitespace().collect();
+            let result = match tokens.as_slice() {
+                [] => Ok(()),
+                [comment, ..] if comment.starts_with('#') => Ok(()),
+                ["move", args @ ..] => builder.add_move(&line, args),
+                ["outcome", args @ ..] => builder.add_outcome(&line, args),
+                ["beats", winner, loser] => {
+                    builder.beats.push((line, winner, loser));
+                    Ok(())
+                }
+                ["beats", args @ ..] => Err(RulesError::WrongArgumentCount {
+                    position: line.start(),
+                    keyword: "beats",
+                    expected: 2,
+                    found: args.len(),
+                }),
+                [keyword, ..] => Err(RulesError::UnknownKeyword {
+                    position: line.position_of(keyword),
+                    found: keyword.to_string(),
+                }),
+            };
+            if let Err(e) = result {
+                errors.push(e);
+            }
+        }
+
+        match builder.build() {
+            Ok(rules) if errors.is_empty() => Ok(rules),
+            Ok(_) => Err(errors),
+            Err(mut build_errors) => {
+                errors.append(&mut build_errors);
+                // The errors about the rules as a whole come last.
+                errors.sort_by_key(|e| e.position().map_or(usize::MAX, |p| p.line));
+                Err(errors)
+            }
+        }
+    }
+
+    // Rock, paper, scissors like in the puzzle.
+    pub fn standard() -> Rules {
+        let input = Input::from_string("rock-paper-scissors.txt", STANDARD_RULES);
+        Rules::parse(&input).expect("the standard rules are valid")
+    }
+
+    pub fn moves(&self) -> impl Iterator<Item = Move> {
+        (0..self.moves.len()).map(Move)
+    }
+
+    pub fn name(&self, m: Move) -> &str {
+        &self.moves[m.0].name
+    }
+
+    pub fn points(&self, m: Move) -> i32 {
+        self.moves[m.0].points
+    }
+
+    pub fn opponent_symbol(&self, m: Move) -> &str {
+        &self.moves[m.0].opponent_symbol
+    }
+
+    pub fn my_symbol(&self, m: Move) -> &str {
+        &self.moves[m.0].symbol
+    }
+
+    // The mapping makes sure the symbols stay unique.
+    pub(crate) fn set_symbols(&mut self, opponent: bool, symbols: Vec<String>) {
+        for (m, symbol) in self.moves.iter_mut().zip(symbols) {
+            if opponent {
+                m.opponent_symbol = symbol;
+            } else {
+                m.symbol = symbol;
+            }
+        }
+    }
+
+    pub(crate) fn set_result_symbols(&mut self, symbols: [Vec<String>; 3]) {
+        for (outcome, symbols) in self.outcomes.iter_mut().zip(symbols) {
+            outcome.symbols = symbols;
+        }
+    }
+
+    pub fn outcome_points(&self, result: GameResult) -> i32 {
+        self.outcomes[result.index()].points
+    }
+
+    pub fn outcome(&self, opponent: Move, me: Move) -> GameResult {
+        if self.beats[me.0][opponent.0] {
+            GameResult::Win
+        } else if self.beats[opponent.0][me.0] {
+            GameResult::Lose
+        } else {
+            GameResult::Draw
+        }
+    }
+
+    pub fn score(&self, opponent: Move, me: Move) -> i32 {
+        self.points(me) + self.outcome_points(self.outcome(opponent, me))
+    }
+
+    fn try_find_move(&self, opponent: Move, wanted_result: GameResult) -> Option<Move> {
+        self.moves()
+            .find(|me| self.outcome(opponent, *me) == wanted_result)
+    }
+
+    // The first move in the order of the rules that gives the wanted result,
+    // parsing the rules makes sure there always is one.
+    pub fn find_move(&self, opponent: Move, wanted_result: GameResult) -> Move {
+        self.try_find_move(opponent, wanted_result)
+            .expect("the rules have a move for every outcome")
+    }
+
+    pub fn opponent_move(&self, symbol: &str) -> Option<Move> {
+        self.moves
+            .iter()
+            .position(|m| m.opponent_symbol == symbol)
+            .map(Move)
+    }
+
+    pub fn my_move(&self, symbol: &str) -> Option<Move> {
+        self.moves.iter().position(|m| m.symbol == symbol).map(Move)
+    }
+
+    pub fn result(&self, symbol: &str) -> Option<GameResult> {
+        GameResult::ALL
+            .into_iter()
+            .find(|r| self.outcomes[r.index()].symbols.iter().any(|s| s == symbol))
+    }
+
+    // "A, B or C", for the error messages
+    pub fn opponent_symbols(&self) -> String {
+        list(self.moves.iter().map(|m| m.opponent_symbol.as_str()))
+    }
+
+    pub fn my_symbols(&self) -> String {
+        list(self.moves.iter().map(|m| m.symbol.as_str()))
+    }
+
+    pub fn result_symbols(&self) -> String {
+        list(
+            self.outcomes
+                .iter()
+                .flat_map(|o| o.symbols.iter().map(String::as_str)),
+        )
+    }
+}
+
+fn list<'a>(items: impl Iterator<Item = &'a str>) -> String {
+    let items: Vec<&str> = items.collect();
+    match items.split_last() {
+        Some((last, [])) => last.to_string(),
+        Some((last, rest)) => format!("{} or {last}", rest.join(", ")),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rules::*;
+    use aoc_input::Position;
+    use std::path::Path;
+
+    fn rpsls() -> Rules {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("rules")
+            .join("rock-paper-scissors-lizard-spock.txt");
+        Rules::parse(&Input::from_file(&path).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_standard_rules() {
+        let rules = Rules::standard();
+        let [rock, paper, scissors] = [0, 1, 2].map(Move);
+        assert_eq!(rules.score(rock, paper), 8);
+        assert_eq!(rules.score(paper, rock), 1);
+        assert_eq!(rules.score(scissors, scissors), 6);
+        assert_eq!(rules.find_move(rock, GameResult::Lose), scissors);
+        assert_eq!(rules.find_move(scissors, GameResult::Win), rock);
+        assert_eq!(rules.opponent_symbols(), "A, B or C");
+    }
+
+    #[test]
+    fn test_rpsls() {
+        let rules = rpsls();
+        let spock = rules.my_move("Z").unwrap();
+        let lizard = rules.opponent_move("D").unwrap();
+        assert_eq!(rules.name(spock), "Spock");
+        assert_eq!(rules.outcome(lizard, spock), GameResult::Lose);
+        assert_eq!(rules.score(lizard, spock), 5);
+        // Rock and Scissors both beat lizard, rock is declared first
+        assert_eq!(rules.name(rules.find_move(lizard, GameResult::Win)), "Rock");
+        assert_eq!(rules.result("W"), Some(GameResult::Lose));
+    }
+
+    #[test]
+    fn test_rules_errors() {
+        let text = "move Rock 1 A X\nmove Paper two B Y\nmove Rock 3 C Z\nbeats Rock Spock\n\
+                    beats Rock Rock\njump\noutcome win 6 Z\noutcome tie 3 Y\n";
+        let errors = Rules::parse(&Input::from_string("rules", text)).unwrap_err();
+        let position = |line, column| Position { line, column };
+        assert_eq!(
+            errors,
+            vec![
+                RulesError::InvalidPoints {
+                    position: position(2, 12),
+                    found: "two".to_owned()
+                },
+                RulesError::Duplicate {
+                    position: position(3, 6),
+                    found: "Rock".to_owned()
+                },
+                RulesError::UnknownMove {
+                    position: position(4, 12),
+                    found: "Spock".to_owned()
+                },
+                RulesError::BeatsItself {
+                    position: position(5, 12),
+                    found: "Rock".to_owned()
+                },
+                RulesError::UnknownKeyword {
+                    position: position(6, 1),
+                    found: "jump".to_owned()
+                },
+                RulesError::UnknownOutcome {
+                    position: position(8, 9),
+                    found: "tie".to_owned()
+                },
+                RulesError::MissingOutcome { outcome: "lose" },
+                RulesError::MissingOutcome { outcome: "draw" },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unreachable_outcome() {
+        // Nothing beats Rock, so there is no way to win against it.
+        let text = "move Rock 1 A X\nmove Paper 2 B Y\nbeats Rock Paper\n\
+                    outcome lose 0 X\noutcome draw 3 Y\noutcome win 6 Z\n";
+        let errors = Rules::parse(&Input::from_string("rules", text)).unwrap_err();
+        assert_eq!(
+            errors,
+            vec![
+                RulesError::Unreachable {
+                    opponent: "Rock".to_owned(),
+                    outcome: "win"
+                },
+                RulesError::Unreachable {
+                    opponent: "Paper".to_owned(),
+                    outcome: "lose"
+                },
+            ]
+        );
+    }
+}
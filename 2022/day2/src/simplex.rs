@@ -0,0 +1,170 @@
+// A dense simplex solver for the small linear programs of the strategy search:
+// maximize c·x with a·x <= b and x >= 0. The right hand side can be negative, then a
+// first phase looks for a feasible start. Bland's rule keeps it from cycling.
+
+const EPS: f64 = 1e-9;
+
+struct Tableau {
+    rows: usize,
+    cols: usize,
+    // the variable of every row and column, the extra variable of phase 1 is None
+    basic: Vec<Option<usize>>,
+    non_basic: Vec<Option<usize>>,
+    d: Vec<Vec<f64>>,
+}
+
+impl Tableau {
+    fn pivot(&mut self, r: usize, s: usize) {
+        let inv = 1.0 / self.d[r][s];
+        for i in 0..self.rows + 2 {
+            if i == r || self.d[i][s] == 0.0 {
+                continue;
+            }
+            let factor = self.d[i][s] * inv;
+            for j in 0..self.cols + 2 {
+                if j != s {
+                    self.d[i][j] -= self.d[r][j] * factor;
+                }
+            }
+            self.d[i][s] = -factor;
+        }
+        for j in 0..self.cols + 2 {
+            if j != s {
+                self.d[r][j] *= inv;
+            }
+        }
+        self.d[r][s] = inv;
+        std::mem::swap(&mut self.basic[r], &mut self.non_basic[s]);
+    }
+
+    // Bland's rule picks the lowest variable, the extra variable of phase 1 first.
+    fn lower(a: Option<usize>, b: Option<usize>) -> bool {
+        match (a, b) {
+            (None, _) => true,
+            (_, None) => false,
+            (Some(a), Some(b)) => a < b,
+        }
+    }
+
+    // False when the objective is unbounded.
+    fn run(&mut self, phase: usize) -> bool {
+        let objective = if phase == 1 { self.rows + 1 } else { self.rows };
+        loop {
+            let mut entering: Option<usize> = None;
+            for j in 0..=self.cols {
+                if phase == 2 && self.non_basic[j].is_none() {
+                    continue;
+                }
+                let better = entering.is_none_or(|s| {
+                    let (dj, ds) = (self.d[objective][j], self.d[objective][s]);
+                    dj < ds - EPS
+                        || (dj <= ds + EPS && Tableau::lower(self.non_basic[j], self.non_basic[s]))
+                });
+                if better {
+                    entering = Some(j);
+                }
+            }
+            let s = entering.expect("there is always a column");
+            if self.d[objective][s] > -EPS {
+                return true;
+            }
+
+            let mut leaving: Option<usize> = None;
+            for i in 0..self.rows {
+                if self.d[i][s] < EPS {
+                    continue;
+                }
+                let better = leaving.is_none_or(|r| {
+                    let ratio_i = self.d[i][self.cols + 1] / self.d[i][s];
+                    let ratio_r = self.d[r][self.cols + 1] / self.d[r][s];
+                    ratio_i < ratio_r - EPS
+                        || (ratio_i <= ratio_r + EPS
+                            && Tableau::lower(self.basic[i], self.basic[r]))
+                });
+                if better {
+                    leaving = Some(i);
+                }
+            }
+            match leaving {
+                Some(r) => self.pivot(r, s),
+                None => return false,
+            }
+        }
+    }
+}
+
+// The best value and x, None when no x meets the rows or the value is unbounded.
+pub fn maximize(a: &[Vec<f64>], b: &[f64], c: &[f64]) -> Option<(f64, Vec<f64>)> {
+    let (rows, cols) = (b.len(), c.len());
+    let mut d = vec![vec![0.0; cols + 2]; rows + 2];
+    for i in 0..rows {
+        d[i][..cols].copy_from_slice(&a[i]);
+        d[i][cols] = -1.0;
+        d[i][cols + 1] = b[i];
+    }
+    for j in 0..cols {
+        d[rows][j] = -c[j];
+    }
+    d[rows + 1][cols] = 1.0;
+    let mut t = Tableau {
+        rows,
+        cols,
+        basic: (cols..cols + rows).map(Some).collect(),
+        non_basic: (0..cols).map(Some).chain([None]).collect(),
+        d,
+    };
+
+    let lowest = (0..rows).min_by(|i, j| t.d[*i][cols + 1].total_cmp(&t.d[*j][cols + 1]));
+    if let Some(r) = lowest.filter(|r| t.d[*r][cols + 1] < -EPS) {
+        // phase 1 minimizes the extra variable, that is only 0 when x = 0 can be moved
+        // into the rows
+        t.pivot(r, cols);
+        if !t.run(1) || t.d[rows + 1][cols + 1] < -EPS {
+            return None;
+        }
+        for i in 0..rows {
+            if t.basic[i].is_none() {
+                let s = (0..=cols)
+                    .min_by(|j, k| t.d[i][*j].total_cmp(&t.d[i][*k]))
+                    .expect("there is always a column");
+                t.pivot(i, s);
+            }
+        }
+    }
+    if !t.run(2) {
+        return None;
+    }
+
+    let mut x = vec![0.0; cols];
+    for i in 0..rows {
+        if let Some(v) = t.basic[i].filter(|v| *v < cols) {
+            x[v] = t.d[i][cols + 1];
+        }
+    }
+    Some((t.d[rows][cols + 1], x))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::simplex::*;
+
+    #[test]
+    fn test_maximize() {
+        // x + y <= 4, x + 3y <= 6, x <= 3: the best is x = 3, y = 1
+        let a = vec![vec![1.0, 1.0], vec![1.0, 3.0], vec![1.0, 0.0]];
+        let (value, x) = maximize(&a, &[4.0, 6.0, 3.0], &[3.0, 2.0]).unwrap();
+        assert!((value - 11.0).abs() < 1e-6);
+        assert!((x[0] - 3.0).abs() < 1e-6 && (x[1] - 1.0).abs() < 1e-6);
+
+        // x + y >= 2 needs the first phase, x = 2, y = 0 is the cheapest
+        let a = vec![vec![-1.0, -1.0], vec![1.0, 0.0], vec![0.0, 1.0]];
+        let (value, _) = maximize(&a, &[-2.0, 5.0, 5.0], &[-1.0, -2.0]).unwrap();
+        assert!((value + 2.0).abs() < 1e-6);
+
+        // x >= 3 and x <= 2
+        assert_eq!(
+            maximize(&[vec![-1.0], vec![1.0]], &[-3.0, 2.0], &[1.0]),
+            None
+        );
+    }
+}
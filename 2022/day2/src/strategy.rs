@@ -0,0 +1,451 @@
+use crate::error::StrategyError;
+use crate::rules::{GameResult, Move, Rules};
+use crate::simplex;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Constraints {
+    // At least this many rounds have to be lost.
+    pub min_losses: usize,
+    // A move can be played at most this many times, moves that are not listed are unlimited.
+    pub max_uses: Vec<(Move, usize)>,
+}
+
+impl Constraints {
+    // "Rock=10,Paper=5", the names are matched without case.
+    pub fn parse_max_uses(rules: &Rules, spec: &str) -> Result<Vec<(Move, usize)>, StrategyError> {
+        let mut max_uses: Vec<(Move, usize)> = Vec::new();
+        for entry in spec.split(',') {
+            let invalid = || StrategyError::InvalidLimit {
+                found: entry.to_owned(),
+            };
+            let (name, count) = entry.split_once('=').ok_or_else(invalid)?;
+            let count: usize = count.trim().parse().map_err(|_| invalid())?;
+            let m = rules
+                .moves()
+                .find(|m| rules.name(*m).eq_ignore_ascii_case(name.trim()))
+                .ok_or_else(|| StrategyError::UnknownMove {
+                    found: name.trim().to_owned(),
+                })?;
+            if max_uses.iter().any(|(limited, _)| *limited == m) {
+                return Err(invalid());
+            }
+            max_uses.push((m, count));
+        }
+
+        Ok(max_uses)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Strategy {
+    pub score: i32,
+    // my move for every round
+    pub moves: Vec<Move>,
+}
+
+// A count of the linear program that can't be more than or less than a whole number,
+// added when branching.
+#[derive(Debug, Clone, Copy)]
+enum Bound {
+    AtMost(usize, f64),
+    AtLeast(usize, f64),
+}
+
+// The order of the rounds does not matter, the score and the number of losses only
+// depend on how often every move is played against every opponent move. Those counts
+// are found with a linear program, and when a count comes out as a fraction the search
+// branches on it being at most the number below or at least the number above.
+// Returns the counts indexed by opponent move then my move.
+fn pair_counts(
+    rules: &Rules,
+    rounds: &[usize],
+    max_uses: &[usize],
+    min_losses: usize,
+) -> Option<Vec<Vec<usize>>> {
+    let moves: Vec<Move> = rules.moves().collect();
+    let n = moves.len();
+    let var = |o: Move, me: Move| o.index() * n + me.index();
+
+    let mut a: Vec<Vec<f64>> = Vec::new();
+    let mut b: Vec<f64> = Vec::new();
+    let mut c = vec![0.0; n * n];
+    for o in &moves {
+        // every round of the opponent move gets exactly one move
+        let mut row = vec![0.0; n * n];
+        for me in &moves {
+            row[var(*o, *me)] = 1.0;
+            c[var(*o, *me)] = f64::from(rules.score(*o, *me));
+        }
+        a.push(row.iter().map(|v| -v).collect());
+        b.push(-(rounds[o.index()] as f64));
+        a.push(row);
+        b.push(rounds[o.index()] as f64);
+    }
+    for me in &moves {
+        let mut row = vec![0.0; n * n];
+        for o in &moves {
+            row[var(*o, *me)] = 1.0;
+        }
+        a.push(row);
+        b.push(max_uses[me.index()] as f64);
+    }
+    let mut row = vec![0.0; n * n];
+    for o in &moves {
+        for me in &moves {
+            if rules.outcome(*o, *me) == GameResult::Lose {
+                row[var(*o, *me)] = -1.0;
+            }
+        }
+    }
+    a.push(row);
+    b.push(-(min_losses as f64));
+
+    let mut best: Option<(i64, Vec<usize>)> = None;
+    let mut branches: Vec<Vec<Bound>> = vec![Vec::new()];
+    while let Some(bounds) = branches.pop() {
+        let (mut a, mut b) = (a.clone(), b.clone());
+        for bound in &bounds {
+            let mut row = vec![0.0; n * n];
+            match *bound {
+                Bound::AtMost(v, max) => {
+                    row[v] = 1.0;
+                    b.push(max);
+                }
+                Bound::AtLeast(v, min) => {
+                    row[v] = -1.0;
+                    b.push(-min);
+                }
+            }
+            a.push(row);
+        }
+        let Some((value, x)) = simplex::maximize(&a, &b, &c) else {
+            continue;
+        };
+        // the score is a whole number, so a branch is only better when its bound is
+        // at least one more
+        if best
+            .as_ref()
+            .is_some_and(|(score, _)| (value + 1e-6).floor() as i64 <= *score)
+        {
+            continue;
+        }
+
+        match x.iter().position(|v| (v - v.round()).abs() > 1e-6) {
+            Some(v) => {
+                let mut below = bounds.clone();
+                below.push(Bound::AtMost(v, x[v].floor()));
+                let mut above = bounds;
+                above.push(Bound::AtLeast(v, x[v].ceil()));
+                branches.push(below);
+                branches.push(above);
+            }
+            None => {
+                let counts: Vec<usize> = x.iter().map(|v| v.round() as usize).collect();
+                let score = counts
+                    .iter()
+                    .zip(&c)
+                    .map(|(count, score)| *count as i64 * *score as i64)
+                    .sum();
+                if best.as_ref().is_none_or(|(s, _)| *s < score) {
+                    best = Some((score, counts));
+                }
+            }
+        }
+    }
+
+    best.map(|(_, counts)| counts.chunks(n).map(<[usize]>::to_vec).collect())
+}
+
+// Finds my moves against the opponent that give the highest total score while the
+// constraints hold.
+pub fn optimal_strategy(
+    rules: &Rules,
+    opponent: &[Move],
+    constraints: &Constraints,
+) -> Result<Strategy, StrategyError> {
+    if constraints.min_losses > opponent.len() {
+        return Err(StrategyError::Infeasible);
+    }
+    let moves: Vec<Move> = rules.moves().collect();
+    let mut rounds = vec![0; moves.len()];
+    for m in opponent {
+        rounds[m.index()] += 1;
+    }
+    // A move can't be played more often than there are rounds.
+    let mut max_uses = vec![opponent.len(); moves.len()];
+    for (m, max) in &constraints.max_uses {
+        max_uses[m.index()] = (*max).min(opponent.len());
+    }
+    let mut counts = pair_counts(rules, &rounds, &max_uses, constraints.min_losses)
+        .ok_or(StrategyError::Infeasible)?;
+
+    // in every round the first move in the rules that still has to be played
+    let mut score = 0;
+    let mut played = Vec::with_capacity(opponent.len());
+    for o in opponent {
+        let left = &mut counts[o.index()];
+        let i = left
+            .iter()
+            .position(|c| *c > 0)
+            .expect("every round has a move");
+        left[i] -= 1;
+        score += rules.score(*o, moves[i]);
+        played.push(moves[i]);
+    }
+    Ok(Strategy {
+        score,
+        moves: played,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::strategy::*;
+    use aoc_input::Input;
+
+    // Tries every sequence of moves, only usable for a few rounds.
+    fn brute_force(rules: &Rules, opponent: &[Move], constraints: &Constraints) -> Option<i32> {
+        let moves: Vec<Move> = rules.moves().collect();
+        let mut best = None;
+        let combinations = moves.len().pow(opponent.len() as u32);
+        for mut n in 0..combinations {
+            let mut mine = Vec::new();
+            for _ in opponent {
+                mine.push(moves[n % moves.len()]);
+                n /= moves.len();
+            }
+
+            let losses = opponent
+                .iter()
+                .zip(&mine)
+                .filter(|(o, m)| rules.outcome(**o, **m) == GameResult::Lose)
+                .count();
+            let within_limits = constraints
+                .max_uses
+                .iter()
+                .all(|(l, max)| mine.iter().filter(|m| *m == l).count() <= *max);
+            if losses < constraints.min_losses || !within_limits {
+                continue;
+            }
+
+            let score: i32 = opponent
+                .iter()
+                .zip(&mine)
+                .map(|(o, m)| rules.score(*o, *m))
+                .sum();
+            best = best.max(Some(score));
+        }
+        best
+    }
+
+    fn opponent(rules: &Rules, symbols: &str) -> Vec<Move> {
+        symbols
+            .chars()
+            .map(|c| rules.opponent_move(&c.to_string()).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_unconstrained() {
+        let rules = Rules::standard();
+        let opponent = opponent(&rules, "ABC");
+        let strategy = optimal_strategy(&rules, &opponent, &Constraints::default()).unwrap();
+
+        // win every round
+        assert_eq!(strategy.score, (2 + 6) + (3 + 6) + (1 + 6));
+        let names: Vec<&str> = strategy.moves.iter().map(|m| rules.name(*m)).collect();
+        assert_eq!(names, vec!["Paper", "Scissors", "Rock"]);
+    }
+
+    #[test]
+    fn test_against_brute_force() {
+        let rules = Rules::standard();
+        let opponent = opponent(&rules, "ABCCABBA");
+        let rock = rules.my_move("X").unwrap();
+        let scissors = rules.my_move("Z").unwrap();
+        let all_constraints = [
+            Constraints {
+                min_losses: 3,
+                max_uses: vec![],
+            },
+            Constraints {
+                min_losses: 0,
+                max_uses: vec![(scissors, 1)],
+            },
+            Constraints {
+                min_losses: 2,
+                max_uses: vec![(rock, 2), (scissors, 2)],
+            },
+        ];
+
+        for constraints in all_constraints {
+            let strategy = optimal_strategy(&rules, &opponent, &constraints).unwrap();
+            assert_eq!(
+                Some(strategy.score),
+                brute_force(&rules, &opponent, &constraints)
+            );
+
+            let replayed: i32 = opponent
+                .iter()
+                .zip(&strategy.moves)
+                .map(|(o, m)| rules.score(*o, *m))
+                .sum();
+            assert_eq!(replayed, strategy.score);
+        }
+    }
+
+    #[test]
+    fn test_infeasible() {
+        let rules = Rules::standard();
+        let opponent = opponent(&rules, "AB");
+        let constraints = Constraints {
+            min_losses: 3,
+            max_uses: vec![],
+        };
+        assert_eq!(
+            optimal_strategy(&rules, &opponent, &constraints),
+            Err(StrategyError::Infeasible)
+        );
+
+        let constraints = Constraints {
+            min_losses: usize::MAX,
+            max_uses: vec![],
+        };
+        assert_eq!(
+            optimal_strategy(&rules, &opponent, &constraints),
+            Err(StrategyError::Infeasible)
+        );
+
+        // only 1 of the 2 rounds can be played
+        let constraints = Constraints {
+            min_losses: 0,
+            max_uses: rules
+                .moves()
+                .enumerate()
+                .map(|(i, m)| (m, usize::from(i == 0)))
+                .collect(),
+        };
+        assert_eq!(
+            optimal_strategy(&rules, &opponent, &constraints),
+            Err(StrategyError::Infeasible)
+        );
+    }
+
+    #[test]
+    fn test_large_limits() {
+        let rules = Rules::standard();
+        let rock = rules.my_move("X").unwrap();
+        let paper = rules.my_move("Y").unwrap();
+
+        // a limit above the number of rounds does not matter
+        let opponent = opponent(&rules, "ABC");
+        let constraints = Constraints {
+            min_losses: 1,
+            max_uses: vec![(rock, 100_000)],
+        };
+        let many_rounds = opponent.repeat(10_000);
+        assert!(optimal_strategy(&rules, &many_rounds, &constraints).is_ok());
+        let constraints = Constraints {
+            min_losses: 1,
+            max_uses: vec![(rock, 100_000)],
+        };
+        let strategy = optimal_strategy(&rules, &opponent, &constraints).unwrap();
+        // losing with scissors against rock costs the least
+        assert_eq!(strategy.score, 3 + (3 + 6) + (1 + 6));
+
+        // the real input has 2500 rounds
+        let opponent = opponent.repeat(834)[..2500].to_vec();
+        let constraints = Constraints {
+            min_losses: 0,
+            max_uses: vec![(rock, 1000), (paper, 1000)],
+        };
+        let strategy = optimal_strategy(&rules, &opponent, &constraints).unwrap();
+        let uses = |m: Move| strategy.moves.iter().filter(|p| **p == m).count();
+        assert!(uses(rock) <= 1000 && uses(paper) <= 1000);
+        // every round can still be won, rock is only played against scissors
+        let all_wins = 2500 * 6 + 834 * 2 + 833 * 3 + 833;
+        assert_eq!(strategy.score, all_wins);
+
+        let constraints = Constraints {
+            min_losses: 1,
+            max_uses: vec![(rock, 1000), (paper, 1000)],
+        };
+        let strategy = optimal_strategy(&rules, &opponent, &constraints).unwrap();
+        assert_eq!(strategy.score, all_wins - 5);
+
+        // paper beats rock only 500 times, the other rocks are tied with rock and
+        // the losses are scissors against rock, that only costs 1 point more
+        let constraints = Constraints {
+            min_losses: 10,
+            max_uses: vec![(paper, 500)],
+        };
+        let strategy = optimal_strategy(&rules, &opponent, &constraints).unwrap();
+        assert_eq!(strategy.moves.iter().filter(|m| **m == paper).count(), 500);
+        assert_eq!(
+            strategy.score,
+            500 * 8 + 324 * 4 + 10 * 3 + 833 * 9 + 833 * 7
+        );
+    }
+
+    #[test]
+    fn test_random_against_brute_force() {
+        let spock = include_str!("../rules/rock-paper-scissors-lizard-spock.txt");
+        let spock = Rules::parse(&Input::from_string("spock", spock)).unwrap();
+        let mut seed: u64 = 42;
+        let mut random = |n: usize| {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (seed >> 33) as usize % n
+        };
+
+        for rules in [Rules::standard(), spock] {
+            let moves: Vec<Move> = rules.moves().collect();
+            for _ in 0..200 {
+                let rounds = 1 + random(5);
+                let opponent: Vec<Move> = (0..rounds).map(|_| moves[random(moves.len())]).collect();
+                let mut constraints = Constraints {
+                    min_losses: random(rounds + 1),
+                    max_uses: Vec::new(),
+                };
+                for m in &moves {
+                    if random(2) == 0 {
+                        constraints.max_uses.push((*m, random(rounds + 1)));
+                    }
+                }
+
+                let strategy = optimal_strategy(&rules, &opponent, &constraints).ok();
+                assert_eq!(
+                    strategy.map(|s| s.score),
+                    brute_force(&rules, &opponent, &constraints),
+                    "{opponent:?} {constraints:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_max_uses() {
+        let rules = Rules::standard();
+        let max_uses = Constraints::parse_max_uses(&rules, "rock=2, Paper=0").unwrap();
+        assert_eq!(
+            max_uses,
+            vec![
+                (rules.my_move("X").unwrap(), 2),
+                (rules.my_move("Y").unwrap(), 0)
+            ]
+        );
+        assert_eq!(
+            Constraints::parse_max_uses(&rules, "Rock"),
+            Err(StrategyError::InvalidLimit {
+                found: "Rock".to_owned()
+            })
+        );
+        assert_eq!(
+            Constraints::parse_max_uses(&rules, "Well=2"),
+            Err(StrategyError::UnknownMove {
+                found: "Well".to_owned()
+            })
+        );
+    }
+}
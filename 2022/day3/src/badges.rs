@@ -0,0 +1,197 @@
+use crate::error::BadgeError;
+use crate::items::ItemSet;
+use crate::Rucksack;
+
+// Finding a partition is an exact cover problem, so the search is cut off after this
+// many steps instead of running for hours on an input without one.
+pub const MAX_STEPS: usize = 1_000_000;
+
+// Groups of rucksacks that share exactly one badge, a group is the indexes of its
+// rucksacks in the input and badges has the priority of the badge of every group.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Partition {
+    pub groups: Vec<Vec<usize>>,
+    pub badges: Vec<i32>,
+    // None when the search was cut off before a second partition was ruled out
+    pub unique: Option<bool>,
+}
+
+impl Partition {
+    pub fn badge_sum(&self) -> i32 {
+        self.badges.iter().sum()
+    }
+}
+
+struct Search<'a> {
+    sets: &'a [ItemSet],
+    group_size: usize,
+    used: Vec<bool>,
+    groups: Vec<Vec<usize>>,
+    // at most 2, that is enough to know if the first one is unique
+    found: Vec<Vec<Vec<usize>>>,
+    steps: usize,
+    cut_off: bool,
+}
+
+impl Search<'_> {
+    // Starts a new group with the first rucksack that is not in a group yet, so every
+    // partition is only found once. Returns true when the search is done.
+    fn next_group(&mut self) -> bool {
+        match self.used.iter().position(|used| !used) {
+            Some(first) => {
+                self.used[first] = true;
+                let done = self.fill(&mut vec![first], self.sets[first]);
+                self.used[first] = false;
+                done
+            }
+            None => {
+                self.found.push(self.groups.clone());
+                self.found.len() == 2
+            }
+        }
+    }
+
+    // Adds rucksacks after the last one in the group while they still have an item in common.
+    fn fill(&mut self, group: &mut Vec<usize>, common: ItemSet) -> bool {
+        self.steps += 1;
+        if self.steps > MAX_STEPS {
+            self.cut_off = true;
+            return true;
+        }
+
+        if group.len() == self.group_size {
+            if common.len() != 1 {
+                return false;
+            }
+            self.groups.push(group.clone());
+            let done = self.next_group();
+            self.groups.pop();
+            return done;
+        }
+
+        let last = group[group.len() - 1];
+        for next in last + 1..self.sets.len() {
+            let shared = common.intersection(self.sets[next]);
+            if self.used[next] || shared.is_empty() {
+                continue;
+            }
+
+            self.used[next] = true;
+            group.push(next);
+            let done = self.fill(group, shared);
+            group.pop();
+            self.used[next] = false;
+            if done {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+// Splits rucksacks in any order into groups of group_size that all share exactly one badge.
+pub fn infer_groups(rucksacks: &[Rucksack], group_size: usize) -> Result<Partition, BadgeError> {
+    if group_size == 0 || !rucksacks.len().is_multiple_of(group_size) {
+        return Err(BadgeError::Uneven {
+            found: rucksacks.len(),
+            group_size,
+        });
+    }
+
+    let sets: Vec<ItemSet> = rucksacks
+        .iter()
+        .map(|r| ItemSet::from_priorities(r))
+        .collect();
+    let mut search = Search {
+        sets: &sets,
+        group_size,
+        used: vec![false; sets.len()],
+        groups: Vec::new(),
+        found: Vec::new(),
+        steps: 0,
+        cut_off: false,
+    };
+    search.next_group();
+
+    let unique = match (search.found.len(), search.cut_off) {
+        (0, true) => {
+            return Err(BadgeError::TooHard {
+                max_steps: MAX_STEPS,
+            })
+        }
+        (0, false) => return Err(BadgeError::NoPartition),
+        (1, true) => None,
+        (1, false) => Some(true),
+        _ => Some(false),
+    };
+
+    let groups = search.found.swap_remove(0);
+    let badges = groups
+        .iter()
+        .map(|group| {
+            let common = ItemSet::intersect_all(group.iter().map(|i| sets[*i]));
+            common.priorities().next().expect("groups share one badge")
+        })
+        .collect();
+    Ok(Partition {
+        groups,
+        badges,
+        unique,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::badges::*;
+    use crate::{parse_items, Priorities};
+    use aoc_input::Input;
+
+    fn rucksacks(text: &str) -> Vec<Rucksack> {
+        parse_items(&Input::from_string("test", text), &Priorities::standard()).unwrap()
+    }
+
+    #[test]
+    fn test_infer_groups() {
+        // the example with the groups mixed up
+        let rucksacks = rucksacks(
+            "wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn\n\
+             vJrwpWtwJgWrhcsFMMfFFhFp\n\
+             ttgJtRGJQctTZtZT\n\
+             jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\n\
+             CrZsJsPPZsGzwwsLwLmpwMDw\n\
+             PmmdzqPrVvPwwTWBwg\n",
+        );
+        let partition = infer_groups(&rucksacks, 3).unwrap();
+
+        assert_eq!(partition.groups, vec![vec![0, 2, 4], vec![1, 3, 5]]);
+        assert_eq!(partition.badges, vec![52, 18]);
+        assert_eq!(partition.badge_sum(), 70);
+        // 0, 3, 5 share q and 1, 2, 4 share J
+        assert_eq!(partition.unique, Some(false));
+    }
+
+    #[test]
+    fn test_uniqueness() {
+        let partition = infer_groups(&rucksacks("abx\ncz\nay\ncw\n"), 2).unwrap();
+        assert_eq!(partition.groups, vec![vec![0, 2], vec![1, 3]]);
+        assert_eq!(partition.badges, vec![1, 3]);
+        assert_eq!(partition.unique, Some(true));
+
+        // a is in every rucksack and nothing else is shared
+        let partition = infer_groups(&rucksacks("ab\nac\nad\nae\n"), 2).unwrap();
+        assert_eq!(partition.groups, vec![vec![0, 1], vec![2, 3]]);
+        assert_eq!(partition.unique, Some(false));
+
+        assert_eq!(
+            infer_groups(&rucksacks("ab\nab\ncd\nce\n"), 2),
+            Err(BadgeError::NoPartition)
+        );
+        assert_eq!(
+            infer_groups(&rucksacks("ab\nac\nad\n"), 2),
+            Err(BadgeError::Uneven {
+                found: 3,
+                group_size: 2
+            })
+        );
+    }
+}
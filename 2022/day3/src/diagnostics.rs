@@ -0,0 +1,175 @@
+use std::fmt;
+
+use crate::items::{common_items, ItemSet};
+use crate::priority::Priorities;
+use crate::{Layout, Rucksack};
+
+// What is wrong or unusual about one rucksack, line is where it is in the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RucksackReport {
+    pub line: usize,
+    // the items that are in every compartment
+    pub shared: ItemSet,
+    // the items that are more than once in the same compartment, for every compartment
+    pub duplicates: Vec<ItemSet>,
+}
+
+// The badges of one group, first_line is the line of the first rucksack in it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroupReport {
+    pub first_line: usize,
+    pub badges: ItemSet,
+}
+
+// Everything the puzzle assumes but does not check: every rucksack shares exactly
+// one item between its compartments and every group exactly one badge.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostics {
+    pub rucksacks: Vec<RucksackReport>,
+    pub groups: Vec<GroupReport>,
+    // to show the items of the sets
+    priorities: Priorities,
+}
+
+fn duplicates(compartment: &[i32]) -> ItemSet {
+    let mut seen = ItemSet::default();
+    let mut duplicates = ItemSet::default();
+    for p in compartment {
+        if seen.contains(*p) {
+            duplicates.insert(*p);
+        }
+        seen.insert(*p);
+    }
+    duplicates
+}
+
+impl Diagnostics {
+    // An incomplete last group is left out.
+    pub fn new(layout: &Layout, priorities: &Priorities, rucksacks: &[Rucksack]) -> Diagnostics {
+        let reports = rucksacks
+            .iter()
+            .enumerate()
+            .map(|(i, rucksack)| {
+                let compartments = layout.split(rucksack);
+                RucksackReport {
+                    line: i + 1,
+                    shared: common_items(&compartments),
+                    duplicates: compartments.iter().map(|c| duplicates(c)).collect(),
+                }
+            })
+            .collect();
+
+        let groups = rucksacks
+            .chunks_exact(layout.group_size)
+            .enumerate()
+            .map(|(i, group)| {
+                let group: Vec<&[i32]> = group.iter().map(Vec::as_slice).collect();
+                GroupReport {
+                    first_line: i * layout.group_size + 1,
+                    badges: common_items(&group),
+                }
+            })
+            .collect();
+
+        Diagnostics {
+            rucksacks: reports,
+            groups,
+            priorities: priorities.clone(),
+        }
+    }
+
+    fn items(&self, set: ItemSet) -> String {
+        set.priorities()
+            .filter_map(|p| self.priorities.item(p))
+            .map(String::from)
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+
+    // Rucksacks and groups that do not have exactly one shared item.
+    pub fn violations(&self) -> usize {
+        let rucksacks = self.rucksacks.iter().filter(|r| r.shared.len() != 1);
+        let groups = self.groups.iter().filter(|g| g.badges.len() != 1);
+        rucksacks.count() + groups.count()
+    }
+}
+
+impl fmt::Display for Diagnostics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for r in &self.rucksacks {
+            write!(f, "line {}: shared [{}]", r.line, self.items(r.shared))?;
+            for (i, duplicates) in r.duplicates.iter().enumerate() {
+                if !duplicates.is_empty() {
+                    write!(
+                        f,
+                        ", duplicates in compartment {} [{}]",
+                        i + 1,
+                        self.items(*duplicates)
+                    )?;
+                }
+            }
+            if r.shared.len() != 1 {
+                write!(f, ", expected 1 shared item, found {}", r.shared.len())?;
+            }
+            writeln!(f)?;
+        }
+
+        for g in &self.groups {
+            if g.badges.len() != 1 {
+                writeln!(
+                    f,
+                    "group at line {}: expected 1 badge, found {} [{}]",
+                    g.first_line,
+                    g.badges.len(),
+                    self.items(g.badges)
+                )?;
+            }
+        }
+
+        writeln!(
+            f,
+            "{} rucksacks, {} groups, {} not with exactly one shared item",
+            self.rucksacks.len(),
+            self.groups.len(),
+            self.violations()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::diagnostics::*;
+    use crate::parse_items;
+    use aoc_input::Input;
+
+    #[test]
+    fn test_diagnostics() {
+        let priorities = Priorities::standard();
+        let input = Input::from_string("test", "aabA\nabab\nxyzX\nabcd\nbAAc\ncdAa\n");
+        let rucksacks = parse_items(&input, &priorities).unwrap();
+        let diagnostics = Diagnostics::new(&Layout::default(), &priorities, &rucksacks);
+        let items = |items: &[i32]| ItemSet::from_priorities(items);
+
+        assert_eq!(
+            diagnostics.rucksacks[0],
+            RucksackReport {
+                line: 1,
+                shared: ItemSet::default(),
+                duplicates: vec![items(&[1]), ItemSet::default()],
+            }
+        );
+        assert_eq!(diagnostics.rucksacks[1].shared, items(&[1, 2]));
+        assert_eq!(diagnostics.groups[1].badges, items(&[3]));
+        assert_eq!(diagnostics.groups[0].badges, ItemSet::default());
+        // rucksacks 1, 2, 3, 4 and 6 and the first group
+        assert_eq!(diagnostics.violations(), 6);
+
+        let text = diagnostics.to_string();
+        assert!(text.contains(
+            "line 1: shared [], duplicates in compartment 1 [a], expected 1 shared item, found 0\n"
+        ));
+        assert!(text.contains("line 2: shared [a b], expected 1 shared item, found 2\n"));
+        assert!(text.contains("line 5: shared [A]\n"));
+        assert!(text.contains("group at line 1: expected 1 badge, found 0 []\n"));
+    }
+}
@@ -0,0 +1,134 @@
+use std::{error, fmt};
+
+use aoc_core::LocatedError;
+use aoc_input::Position;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    InvalidItem {
+        position: Position,
+        found: char,
+        expected: String,
+    },
+    UnevenRucksack {
+        position: Position,
+        found: usize,
+        compartments: usize,
+    },
+    IncompleteGroup {
+        position: Position,
+        found: usize,
+        group_size: usize,
+    },
+    NoSharedItem {
+        position: Position,
+    },
+    NoBadge {
+        position: Position,
+    },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::InvalidItem {
+                found, expected, ..
+            } => write!(f, "expected an item {expected}, found '{found}'"),
+            ParseError::UnevenRucksack {
+                found,
+                compartments,
+                ..
+            } => write!(
+                f,
+                "expected a multiple of {compartments} items to fill the compartments, found {found}"
+            ),
+            ParseError::IncompleteGroup {
+                found, group_size, ..
+            } => write!(
+                f,
+                "expected a group of {group_size} rucksacks, found {found} at the end"
+            ),
+            ParseError::NoSharedItem { .. } => {
+                write!(f, "expected an item in every compartment, found none")
+            }
+            ParseError::NoBadge { .. } => {
+                write!(
+                    f,
+                    "expected a badge in every rucksack of the group, found none"
+                )
+            }
+        }
+    }
+}
+
+impl error::Error for ParseError {}
+
+impl LocatedError for ParseError {
+    fn position(&self) -> Option<Position> {
+        match self {
+            ParseError::InvalidItem { position, .. }
+            | ParseError::UnevenRucksack { position, .. }
+            | ParseError::IncompleteGroup { position, .. }
+            | ParseError::NoSharedItem { position }
+            | ParseError::NoBadge { position } => Some(*position),
+        }
+    }
+}
+
+// Errors in a priority table given on the command line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PriorityError {
+    Duplicate { found: char },
+    TooMany { found: usize, max: usize },
+    Empty,
+}
+
+impl fmt::Display for PriorityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PriorityError::Duplicate { found } => {
+                write!(f, "expected every item once, found '{found}' again")
+            }
+            PriorityError::TooMany { found, max } => {
+                write!(f, "expected at most {max} items, found {found}")
+            }
+            PriorityError::Empty => write!(f, "expected at least one item, found none"),
+        }
+    }
+}
+
+impl error::Error for PriorityError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BadgeError {
+    Uneven { found: usize, group_size: usize },
+    NoPartition,
+    TooHard { max_steps: usize },
+}
+
+impl fmt::Display for BadgeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BadgeError::Uneven { found, group_size } => {
+                write!(
+                    f,
+                    "expected a multiple of {group_size} rucksacks, found {found}"
+                )
+            }
+            BadgeError::NoPartition => {
+                write!(
+                    f,
+                    "expected groups that share exactly one badge, found none"
+                )
+            }
+            BadgeError::TooHard { max_steps } => {
+                write!(
+                    f,
+                    "expected groups that share exactly one badge, found none in {max_steps} steps"
+                )
+            }
+        }
+    }
+}
+
+impl error::Error for BadgeError {}
@@ -0,0 +1,122 @@
+// A set of items stored as bits, bit p is set when the item with priority p is in the set.
+// There are at most 63 priorities, so they all fit in one u64.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ItemSet(u64);
+
+impl ItemSet {
+    pub fn from_priorities(priorities: &[i32]) -> ItemSet {
+        let mut set = ItemSet::default();
+        for p in priorities {
+            set.insert(*p);
+        }
+        set
+    }
+
+    // Panics when the priority is negative or 64 or more, Priorities never hands
+    // those out.
+    pub fn insert(&mut self, priority: i32) {
+        let bit = bit(priority)
+            .unwrap_or_else(|| panic!("priority {priority} does not fit in an ItemSet"));
+        self.0 |= bit;
+    }
+
+    // False for a priority that can't be in a set.
+    pub fn contains(self, priority: i32) -> bool {
+        bit(priority).is_some_and(|bit| self.0 & bit != 0)
+    }
+
+    pub fn intersection(self, other: ItemSet) -> ItemSet {
+        ItemSet(self.0 & other.0)
+    }
+
+    // The items that are in every set, no sets at all have no items in common.
+    pub fn intersect_all(sets: impl IntoIterator<Item = ItemSet>) -> ItemSet {
+        sets.into_iter()
+            .reduce(ItemSet::intersection)
+            .unwrap_or_default()
+    }
+
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    // From lowest to highest.
+    pub fn priorities(self) -> impl Iterator<Item = i32> {
+        (0..u64::BITS as i32).filter(move |p| self.0 & (1 << p) != 0)
+    }
+}
+
+fn bit(priority: i32) -> Option<u64> {
+    1u64.checked_shl(u32::try_from(priority).ok()?)
+}
+
+// The items that all the compartments or rucksacks have in common.
+pub fn common_items(parts: &[&[i32]]) -> ItemSet {
+    ItemSet::intersect_all(parts.iter().map(|p| ItemSet::from_priorities(p)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::items::*;
+    use crate::Priorities;
+
+    fn rucksack(items: &str) -> Vec<i32> {
+        let priorities = Priorities::standard();
+        items
+            .chars()
+            .map(|c| priorities.priority(c).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_item_set() {
+        let set = ItemSet::from_priorities(&rucksack("vJrwpWtwJgWr"));
+        assert!(set.contains(36) && set.contains(16));
+        assert!(!set.contains(1) && !set.contains(10));
+        assert_eq!(set.len(), 8);
+
+        let common = set.intersection(ItemSet::from_priorities(&rucksack("hcsFMMfFFhFp")));
+        assert_eq!(common.priorities().collect::<Vec<i32>>(), vec![16]);
+
+        let mut set = ItemSet::default();
+        set.insert(63);
+        assert!(set.contains(63));
+        assert!(!set.contains(64) && !set.contains(-1));
+    }
+
+    #[test]
+    #[should_panic(expected = "priority 64 does not fit in an ItemSet")]
+    fn test_insert_too_high() {
+        ItemSet::default().insert(64);
+    }
+
+    #[test]
+    #[should_panic(expected = "priority -1 does not fit in an ItemSet")]
+    fn test_insert_negative() {
+        ItemSet::default().insert(-1);
+    }
+
+    #[test]
+    fn test_common_items() {
+        let group = [
+            rucksack("vJrwpWtwJgWrhcsFMMfFFhFp"),
+            rucksack("jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL"),
+            rucksack("PmmdzqPrVvPwwTWBwg"),
+        ];
+        let group: Vec<&[i32]> = group.iter().map(Vec::as_slice).collect();
+        assert_eq!(
+            common_items(&group).priorities().collect::<Vec<i32>>(),
+            vec![18]
+        );
+        assert_eq!(
+            common_items(&group[..1]),
+            ItemSet::from_priorities(group[0])
+        );
+        assert!(common_items(&[&rucksack("abc"), &rucksack("ABC")]).is_empty());
+        assert!(common_items(&[]).is_empty());
+    }
+}
@@ -0,0 +1,312 @@
+use aoc_core::{Answer, Solution};
+use aoc_input::{Input, Position};
+
+mod badges;
+mod diagnostics;
+mod error;
+mod items;
+mod priority;
+
+pub use badges::{infer_groups, Partition, MAX_STEPS};
+pub use diagnostics::{Diagnostics, GroupReport, RucksackReport};
+pub use error::{BadgeError, ParseError, PriorityError};
+pub use items::{common_items, ItemSet};
+pub use priority::{Priorities, MAX_ITEMS};
+
+// The priorities of the items in a rucksack, in the order they are on the line.
+pub type Rucksack = Vec<i32>;
+
+// The priority of the item all the parts share, the lowest one if there are several.
+fn common_priority(parts: &[&[i32]]) -> i32 {
+    common_items(parts)
+        .priorities()
+        .next()
+        .expect("checked by Layout::check")
+}
+
+// The priority in the standard table, None for anything that is not an item.
+pub fn character_score(input: char) -> Option<i32> {
+    Priorities::standard().priority(input)
+}
+
+// How the rucksacks are split up, part1 splits every rucksack in compartments
+// of the same size and part2 takes groups of consecutive rucksacks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Layout {
+    pub compartments: usize,
+    pub group_size: usize,
+}
+
+impl Default for Layout {
+    fn default() -> Layout {
+        Layout {
+            compartments: 2,
+            group_size: 3,
+        }
+    }
+}
+
+impl Layout {
+    // Every rucksack has to fill all its compartments and the last group has to be
+    // complete, otherwise items would be left out. Without a shared item or a badge
+    // there is no priority to add up. Rucksack i is on line i + 1.
+    pub fn check(&self, rucksacks: &[Rucksack]) -> Result<(), Vec<ParseError>> {
+        let mut errors = Vec::new();
+        let start = |line| Position { line, column: 1 };
+        for (i, rucksack) in rucksacks.iter().enumerate() {
+            let found = rucksack.len();
+            if found % self.compartments != 0 {
+                errors.push(ParseError::UnevenRucksack {
+                    position: start(i + 1),
+                    found,
+                    compartments: self.compartments,
+                });
+            } else if common_items(&self.split(rucksack)).is_empty() {
+                errors.push(ParseError::NoSharedItem {
+                    position: start(i + 1),
+                });
+            }
+        }
+
+        for (i, group) in rucksacks.chunks_exact(self.group_size).enumerate() {
+            let group: Vec<&[i32]> = group.iter().map(Vec::as_slice).collect();
+            if common_items(&group).is_empty() {
+                errors.push(ParseError::NoBadge {
+                    position: start(i * self.group_size + 1),
+                });
+            }
+        }
+
+        let leftover = rucksacks.len() % self.group_size;
+        if leftover != 0 {
+            errors.push(ParseError::IncompleteGroup {
+                position: start(rucksacks.len() - leftover + 1),
+                found: leftover,
+                group_size: self.group_size,
+            });
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    // When the items can't be split evenly the last compartment gets the rest.
+    pub fn split<'a>(&self, rucksack: &'a [i32]) -> Vec<&'a [i32]> {
+        let size = rucksack.len() / self.compartments;
+        (0..self.compartments)
+            .map(|i| {
+                let end = if i + 1 == self.compartments {
+                    rucksack.len()
+                } else {
+                    (i + 1) * size
+                };
+                &rucksack[i * size..end]
+            })
+            .collect()
+    }
+
+    pub fn part1(&self, rucksacks: &[Rucksack]) -> i32 {
+        rucksacks
+            .iter()
+            .map(|r| common_priority(&self.split(r)))
+            .sum()
+    }
+
+    pub fn part2(&self, rucksacks: &[Rucksack]) -> i32 {
+        rucksacks
+            .chunks_exact(self.group_size)
+            .map(|group| {
+                let group: Vec<&[i32]> = group.iter().map(Vec::as_slice).collect();
+                common_priority(&group)
+            })
+            .sum()
+    }
+}
+
+// One rucksack on every line, the layout is only checked when all the items are valid.
+pub fn parse_rucksacks(
+    input: &Input,
+    priorities: &Priorities,
+    layout: &Layout,
+) -> Result<Vec<Rucksack>, Vec<ParseError>> {
+    let rucksacks = parse_items(input, priorities)?;
+    layout.check(&rucksacks)?;
+    Ok(rucksacks)
+}
+
+// Only checks the items, not if they can be split up. Every char is an item,
+// so the columns count chars and not bytes.
+pub fn parse_items(
+    input: &Input,
+    priorities: &Priorities,
+) -> Result<Vec<Rucksack>, Vec<ParseError>> {
+    let mut rucksacks = Vec::new();
+    let mut errors = Vec::new();
+    for line in input.lines() {
+        let mut rucksack = Vec::with_capacity(line.text.len());
+        for (column, c) in line.text.chars().enumerate() {
+            match priorities.priority(c) {
+                Some(p) => rucksack.push(p),
+                None => errors.push(ParseError::InvalidItem {
+                    position: Position {
+                        line: line.number,
+                        column: column + 1,
+                    },
+                    found: c,
+                    expected: priorities.describe(),
+                }),
+            }
+        }
+        rucksacks.push(rucksack);
+    }
+
+    if errors.is_empty() {
+        Ok(rucksacks)
+    } else {
+        Err(errors)
+    }
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = Vec<Rucksack>;
+    type Error = ParseError;
+
+    fn parse(input: &Input) -> Result<Vec<Rucksack>, Vec<ParseError>> {
+        parse_rucksacks(input, &Priorities::standard(), &Layout::default())
+    }
+
+    fn part1(lines: &Vec<Rucksack>) -> Answer {
+        Answer::from(Layout::default().part1(lines))
+    }
+
+    fn part2(lines: &Vec<Rucksack>) -> Answer {
+        Answer::from(Layout::default().part2(lines))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn test_character_score() {
+        let char_c = 'c';
+        let expected_score_c = Some(3);
+        let score_c = character_score(char_c);
+        assert_eq!(expected_score_c, score_c);
+
+        let char_capital_c = 'C';
+        let expected_score_capital_c = Some(29);
+        let score_capital_c = character_score(char_capital_c);
+        assert_eq!(expected_score_capital_c, score_capital_c);
+
+        // the first byte of 'é' is not an item either
+        assert_eq!(character_score('é'), None);
+        assert_eq!(character_score('1'), None);
+    }
+
+    #[test]
+    fn test_layout() {
+        let input = Input::from_string("test", "abcXa\nXXX\naXbXcX\nab\n");
+        let rucksacks = parse_items(&input, &Priorities::standard()).unwrap();
+        let layout = Layout {
+            compartments: 3,
+            group_size: 3,
+        };
+        assert_eq!(
+            layout.check(&rucksacks).unwrap_err(),
+            vec![
+                ParseError::UnevenRucksack {
+                    position: Position { line: 1, column: 1 },
+                    found: 5,
+                    compartments: 3,
+                },
+                ParseError::UnevenRucksack {
+                    position: Position { line: 4, column: 1 },
+                    found: 2,
+                    compartments: 3,
+                },
+                ParseError::IncompleteGroup {
+                    position: Position { line: 4, column: 1 },
+                    found: 1,
+                    group_size: 3,
+                },
+            ]
+        );
+
+        let layout = Layout {
+            compartments: 3,
+            group_size: 2,
+        };
+        let rucksacks = &rucksacks[1..3];
+        assert!(layout.check(rucksacks).is_ok());
+        assert_eq!(
+            layout.split(&[1, 2, 3, 4, 5, 6, 7]),
+            vec![&[1, 2][..], &[3, 4], &[5, 6, 7]]
+        );
+        // X is in every compartment and in both rucksacks
+        assert_eq!(layout.part2(rucksacks), 50);
+
+        let input = Input::from_string("test", "abca\nab\n");
+        assert_eq!(Day3::parse(&input).unwrap_err().len(), 2);
+        let input = Input::from_string("test", "abcA\naa\nbb\n");
+        assert_eq!(
+            Day3::parse(&input).unwrap_err(),
+            vec![
+                ParseError::NoSharedItem {
+                    position: Position { line: 1, column: 1 },
+                },
+                ParseError::NoBadge {
+                    position: Position { line: 1, column: 1 },
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_items() {
+        let priorities = Priorities::new("aé€").unwrap();
+        let input = Input::from_string("test", "a€é\néxa\n");
+        assert_eq!(
+            parse_items(&input, &priorities).unwrap_err(),
+            vec![ParseError::InvalidItem {
+                position: Position { line: 2, column: 2 },
+                found: 'x',
+                expected: "a, é or €".to_owned(),
+            }]
+        );
+
+        let input = Input::from_string("test", "a€é\né€\n");
+        assert_eq!(
+            parse_items(&input, &priorities).unwrap(),
+            vec![vec![1, 3, 2], vec![2, 3]]
+        );
+
+        let errors = Day3::parse(&Input::from_string("test", "abcé\n")).unwrap_err();
+        assert_eq!(
+            errors[0].to_string(),
+            "expected an item a-z or A-Z, found 'é'"
+        );
+    }
+
+    #[test]
+    fn test_example_part1() {
+        assert_eq!(
+            Day3::part1(&aoc_core::example::<Day3>(env!("CARGO_MANIFEST_DIR"))),
+            Answer::from(157)
+        );
+    }
+
+    #[test]
+    fn test_example_part2() {
+        assert_eq!(
+            Day3::part2(&aoc_core::example::<Day3>(env!("CARGO_MANIFEST_DIR"))),
+            Answer::from(70)
+        );
+    }
+}
@@ -1,122 +1,92 @@
-use std::{
-    fs::File,
-    io::{self, BufRead, BufReader, Lines}, path::Path
-};
+use std::path::Path;
 
-fn read_lines(filename: &std::path::Path) -> io::Result<Lines<BufReader<File>>> {
-    let file = File::open(filename)?;
-    let reader = BufReader::new(file);
-    let lines = reader.lines();
-    Ok(lines)
-}
+use aoc_core::{format_error, init_logging, read_or_exit};
+use clap::Parser;
+use day3::{
+    infer_groups, parse_items, parse_rucksacks, Diagnostics, Layout, ParseError, Priorities,
+    Rucksack,
+};
 
-// these 2 functions can be merged into a common one.
-fn find_common_char(left: &str, right: &str) -> char {
-    left.chars().find(|&c| {return right.contains(c)})
-        .expect("No common character found between the two strings!")
-}
-fn find_common_char3(left: &str, middle: &str, right: &str) -> char
-{
-    let common: Vec<char> = left.chars()
-        .filter(|x| middle.contains(x.clone()))
-        .filter(|x|right.contains(x.clone()))
-        .collect();
-        
-    if  common.len() == 0 {
-        panic!("no common chars found in group");
-    }
+#[derive(Parser, Debug)]
+struct Args {
+    /// Number of rucksacks in a group for part2
+    #[arg(long, default_value_t = 3, value_parser = clap::value_parser!(u32).range(1..))]
+    group_size: u32,
 
-    return common[0]
-}
+    /// Number of compartments in a rucksack for part1
+    #[arg(long, default_value_t = 2, value_parser = clap::value_parser!(u32).range(1..))]
+    compartments: u32,
 
-fn encode(c: char) -> u8{
-    let mut buff : [u8; 4] = [0,0,0,0];
-    c.encode_utf8(&mut buff); // 97
-    return buff[0];
-}
+    /// Report the shared and duplicate items of every rucksack instead of the answers
+    #[arg(long)]
+    diagnostics: bool,
 
-fn character_score(input: char) -> i32 {
-    let a = encode('a'); // 97
-    let z = encode('z'); // 122
-    let A = encode('A'); // 65
-    let Z = encode('Z'); // 90
+    /// The items from lowest to highest priority, defaults to a-z followed by A-Z
+    #[arg(long)]
+    priorities: Option<String>,
 
-    let input = encode(input);
+    /// The rucksacks are in any order, find groups that share exactly one badge
+    #[arg(long)]
+    unordered: bool,
+}
 
-    if input >= a && input <= z{
-        // a-z have priority 1-26
-        return (input-a+1) as i32;
-    }
-    else if input >= A && input <= Z{
-        // a-z have priority 27-52
-        return ((input-A) + 27) as i32;
+fn main() {
+    init_logging(0);
+    let args = Args::parse();
+    let layout = Layout {
+        compartments: args.compartments as usize,
+        group_size: args.group_size as usize,
+    };
+
+    let priorities = match &args.priorities {
+        Some(alphabet) => Priorities::new(alphabet).unwrap_or_else(|e| {
+            eprintln!("--priorities: {e}");
+            std::process::exit(1);
+        }),
+        None => Priorities::standard(),
+    };
+
+    let input = read_or_exit(Path::new("input.txt"));
+    let exit_on_errors = |errors: Vec<ParseError>| -> Vec<Rucksack> {
+        for e in &errors {
+            eprintln!("{}", format_error(&input, e));
+        }
+        std::process::exit(1);
+    };
+
+    if args.diagnostics {
+        let lines = parse_items(&input, &priorities).unwrap_or_else(exit_on_errors);
+        print!("{}", Diagnostics::new(&layout, &priorities, &lines));
+        return;
     }
-    panic!("invalid character provided in character_score(...) function");
-}
 
-fn part1(lines: Vec<String>)
-{
-    let split_lines = lines.iter()
-        .map(|x| {
-            let half_length = x.len()/2;
-            let (left, right) =  x.split_at(half_length);
-            return (left, right);
+    if args.unordered {
+        let lines = parse_items(&input, &priorities).unwrap_or_else(exit_on_errors);
+        let partition = infer_groups(&lines, layout.group_size).unwrap_or_else(|e| {
+            eprintln!("{e}");
+            std::process::exit(1);
         });
 
-    let priorities = split_lines
-        .map(|(left , right)| {return find_common_char(left, right)})
-        .map(|x: char| character_score(x)) ;
-    
-    let priorities_sum: i32 = priorities.sum();
-
-
-    println!("part1: The sum of the priorities={priorities_sum}"); // 7701
-}
-
-fn part2(lines: Vec<String>)
-{
-    let num_groups = lines.len()/3;
-    let priorities = (0..num_groups).map(|i| {
-        let left = lines[i*3].as_str();
-        let middle = lines[i*3 + 1].as_str();
-        let right = lines[i*3 + 2].as_str();
-        return find_common_char3(left, middle, right);
-    }).map(character_score);
-
-    let total_score: i32 = priorities.sum();
-
-    println!("part2: The sum of all triple groups is {total_score}");
-}
+        for (group, badge) in partition.groups.iter().zip(&partition.badges) {
+            let group: Vec<String> = group.iter().map(|i| (i + 1).to_string()).collect();
+            let badge = priorities.item(*badge).expect("badges are items");
+            println!("lines {}: badge {badge}", group.join(", "));
+        }
+        println!("The sum of the badges is {}", partition.badge_sum());
+        match partition.unique {
+            Some(true) => println!("these are the only groups"),
+            Some(false) => println!("other groups are possible too"),
+            None => println!("other groups might be possible, the search was cut off"),
+        }
+        return;
+    }
 
-fn main() {
-    let p = Path::new("input.txt");
-    let lines: Vec<String> = read_lines(p)
-        .expect("unable to read input file")
-        // This filter and map should be a monadic bind but no idea how to do this in rust.
-        .filter(|x|x.is_ok())
-        .map(|x|x.unwrap())
-        .collect();
+    let lines = parse_rucksacks(&input, &priorities, &layout).unwrap_or_else(exit_on_errors);
 
-    // The iterators always take ownership, and the temporary has to outlive the first expression 
-    // expressions. So sadly we have to clone here. (as far as I know)
-    part1(lines.clone());
-    part2(lines.clone());
+    println!("part1: The sum of the priorities={}", layout.part1(&lines));
+    println!(
+        "part2: The sum of all groups of {} is {}",
+        layout.group_size,
+        layout.part2(&lines)
+    );
 }
-
-#[cfg(test)]
-mod tests{
-    use crate::*;
-
-    #[test]
-    fn test_character_score(){
-        let char_c = 'c';
-        let expected_score_c: i32 = 3;
-        let score_c = character_score(char_c);
-        assert_eq!(expected_score_c, score_c);
-
-        let char_capital_c = 'C';
-        let expected_score_capital_c = 29;
-        let score_capital_c = character_score(char_capital_c);
-        assert_eq!(expected_score_capital_c, score_capital_c);
-    }
-}
\ No newline at end of file
@@ -0,0 +1,109 @@
+use crate::error::PriorityError;
+
+// An ItemSet has a bit for every priority and bit 0 is not used.
+pub const MAX_ITEMS: usize = 63;
+
+// The item at index i has priority i + 1, items can be any char.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Priorities {
+    items: Vec<char>,
+}
+
+impl Priorities {
+    // a-z have priority 1-26 and A-Z 27-52, like in the puzzle.
+    pub fn standard() -> Priorities {
+        Priorities {
+            items: ('a'..='z').chain('A'..='Z').collect(),
+        }
+    }
+
+    // The items from lowest to highest priority, like "abcABC".
+    pub fn new(alphabet: &str) -> Result<Priorities, PriorityError> {
+        let mut items: Vec<char> = Vec::new();
+        for c in alphabet.chars() {
+            if items.contains(&c) {
+                return Err(PriorityError::Duplicate { found: c });
+            }
+            items.push(c);
+        }
+
+        if items.is_empty() {
+            return Err(PriorityError::Empty);
+        }
+        if items.len() > MAX_ITEMS {
+            return Err(PriorityError::TooMany {
+                found: items.len(),
+                max: MAX_ITEMS,
+            });
+        }
+        Ok(Priorities { items })
+    }
+
+    pub fn priority(&self, item: char) -> Option<i32> {
+        self.items
+            .iter()
+            .position(|c| *c == item)
+            .map(|i| i as i32 + 1)
+    }
+
+    pub fn item(&self, priority: i32) -> Option<char> {
+        let index = usize::try_from(priority).ok()?.checked_sub(1)?;
+        self.items.get(index).copied()
+    }
+
+    // "a-z or A-Z", runs of at least 3 consecutive chars are written as a range.
+    pub fn describe(&self) -> String {
+        let mut runs: Vec<(char, char)> = Vec::new();
+        for c in &self.items {
+            match runs.last_mut() {
+                Some((_, last)) if u32::from(*last) + 1 == u32::from(*c) => *last = *c,
+                _ => runs.push((*c, *c)),
+            }
+        }
+
+        let mut parts: Vec<String> = Vec::new();
+        for (first, last) in runs {
+            if u32::from(last) - u32::from(first) >= 2 {
+                parts.push(format!("{first}-{last}"));
+            } else {
+                parts.extend((first..=last).map(String::from));
+            }
+        }
+        match parts.split_last() {
+            Some((last, rest)) if !rest.is_empty() => format!("{} or {last}", rest.join(", ")),
+            _ => parts.join(""),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::priority::*;
+
+    #[test]
+    fn test_priorities() {
+        let standard = Priorities::standard();
+        assert_eq!(standard.priority('p'), Some(16));
+        assert_eq!(standard.priority('L'), Some(38));
+        assert_eq!(standard.priority('é'), None);
+        assert_eq!(standard.item(52), Some('Z'));
+        assert_eq!(standard.item(0), None);
+        assert_eq!(standard.describe(), "a-z or A-Z");
+
+        let custom = Priorities::new("xyzéß").unwrap();
+        assert_eq!(custom.priority('é'), Some(4));
+        assert_eq!(custom.priority('a'), None);
+        assert_eq!(custom.describe(), "x-z, é or ß");
+
+        assert_eq!(
+            Priorities::new("abca"),
+            Err(PriorityError::Duplicate { found: 'a' })
+        );
+        assert_eq!(Priorities::new(""), Err(PriorityError::Empty));
+        let too_many: String = ('\u{100}'..='\u{140}').collect();
+        assert_eq!(
+            Priorities::new(&too_many),
+            Err(PriorityError::TooMany { found: 65, max: 63 })
+        );
+    }
+}
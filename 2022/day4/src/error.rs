@@ -0,0 +1,44 @@
+use std::{error, fmt};
+
+use aoc_core::LocatedError;
+use aoc_input::Position;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    MissingSeparator {
+        position: Position,
+        found: String,
+        expected: char,
+    },
+    InvalidNumber {
+        position: Position,
+        found: String,
+    },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::MissingSeparator {
+                found, expected, ..
+            } => write!(
+                f,
+                "expected two parts separated by '{expected}', found '{found}'"
+            ),
+            ParseError::InvalidNumber { found, .. } => {
+                write!(f, "expected a section number, found '{found}'")
+            }
+        }
+    }
+}
+
+impl error::Error for ParseError {}
+
+impl LocatedError for ParseError {
+    fn position(&self) -> Option<Position> {
+        match self {
+            ParseError::MissingSeparator { position, .. } => Some(*position),
+            ParseError::InvalidNumber { position, .. } => Some(*position),
+        }
+    }
+}
@@ -0,0 +1,139 @@
+use aoc_core::{Answer, Solution};
+use aoc_input::{Input, Line};
+
+mod error;
+
+pub use error::ParseError;
+
+#[derive(Debug)]
+pub struct Task {
+    min: i32,
+    max: i32,
+}
+
+impl Task {
+    // Parses a range like "2-4", the input is a part of the line.
+    fn from_str(line: &Line, input: &str) -> Result<Task, Vec<ParseError>> {
+        let split: Vec<&str> = input.split('-').collect();
+        if split.len() != 2 {
+            return Err(vec![ParseError::MissingSeparator {
+                position: line.position_of(input),
+                found: input.to_owned(),
+                expected: '-',
+            }]);
+        }
+
+        let parse_number = |s: &str| {
+            s.parse::<i32>().map_err(|_| ParseError::InvalidNumber {
+                position: line.position_of(s),
+                found: s.to_owned(),
+            })
+        };
+
+        match (parse_number(split[0]), parse_number(split[1])) {
+            (Ok(min), Ok(max)) => Ok(Task { min, max }),
+            (min, max) => Err(min.err().into_iter().chain(max.err()).collect()),
+        }
+    }
+
+    fn fully_contains(&self, other: &Task) -> bool {
+        other.min >= self.min && other.max <= self.max
+    }
+
+    fn in_range(&self, n: i32) -> bool {
+        n >= self.min && n <= self.max
+    }
+
+    fn overlaps(&self, other: &Task) -> bool {
+        self.in_range(other.min) || self.in_range(other.max)
+    }
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = Vec<(Task, Task)>;
+    type Error = ParseError;
+
+    fn parse(input: &Input) -> Result<Vec<(Task, Task)>, Vec<ParseError>> {
+        let mut pairs = Vec::new();
+        let mut errors = Vec::new();
+        for l in input.lines() {
+            let Some((left, right)) = l.text.split_once(',') else {
+                errors.push(ParseError::MissingSeparator {
+                    position: l.start(),
+                    found: l.text.to_owned(),
+                    expected: ',',
+                });
+                continue;
+            };
+
+            match (Task::from_str(&l, left), Task::from_str(&l, right)) {
+                (Ok(left), Ok(right)) => pairs.push((left, right)),
+                (left, right) => {
+                    errors.extend(left.err().into_iter().flatten());
+                    errors.extend(right.err().into_iter().flatten());
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(pairs)
+        } else {
+            Err(errors)
+        }
+    }
+
+    fn part1(parsed_lines: &Vec<(Task, Task)>) -> Answer {
+        let number_of_fully_contained_pairs: i32 = parsed_lines
+            .iter()
+            .map(|(left, right)| {
+                let fully_contains = left.fully_contains(right) || right.fully_contains(left);
+                if fully_contains {
+                    log::trace!("left={left:?} right={right:?} fully contains");
+                    return 1;
+                }
+                0
+            })
+            .sum();
+
+        Answer::from(number_of_fully_contained_pairs)
+    }
+
+    fn part2(parsed_lines: &Vec<(Task, Task)>) -> Answer {
+        let number_of_overlapping_pairs: i32 = parsed_lines
+            .iter()
+            .map(|(left, right)| {
+                let overlaps = left.overlaps(right) || right.overlaps(left);
+                if overlaps {
+                    log::trace!("left={left:?} right={right:?} overlaps");
+                    return 1;
+                }
+                0
+            })
+            .sum();
+
+        Answer::from(number_of_overlapping_pairs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn test_example_part1() {
+        assert_eq!(
+            Day4::part1(&aoc_core::example::<Day4>(env!("CARGO_MANIFEST_DIR"))),
+            Answer::from(2)
+        );
+    }
+
+    #[test]
+    fn test_example_part2() {
+        assert_eq!(
+            Day4::part2(&aoc_core::example::<Day4>(env!("CARGO_MANIFEST_DIR"))),
+            Answer::from(4)
+        );
+    }
+}
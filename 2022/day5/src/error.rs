@@ -0,0 +1,69 @@
+use std::{error, fmt};
+
+use aoc_core::LocatedError;
+use aoc_input::Position;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    MissingSection {
+        found: usize,
+    },
+    UnexpectedToken {
+        position: Position,
+        found: String,
+        expected: &'static str,
+    },
+    InvalidStack {
+        position: Position,
+        found: u32,
+        stack_count: usize,
+    },
+    SameStack {
+        position: Position,
+    },
+    NotEnoughCrates {
+        position: Position,
+        needed: u32,
+        available: usize,
+    },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::MissingSection { found } => write!(
+                f,
+                "expected the stacks and the moves separated by an empty line, found {found} section(s)"
+            ),
+            ParseError::UnexpectedToken {
+                found, expected, ..
+            } => write!(f, "expected {expected}, found '{found}'"),
+            ParseError::InvalidStack {
+                found, stack_count, ..
+            } => write!(f, "expected a stack 1-{stack_count}, found {found}"),
+            ParseError::SameStack { .. } => {
+                write!(f, "a move needs different source and target stacks")
+            }
+            ParseError::NotEnoughCrates {
+                needed, available, ..
+            } => write!(
+                f,
+                "{needed} crates needed but only {available} available on the stack"
+            ),
+        }
+    }
+}
+
+impl error::Error for ParseError {}
+
+impl LocatedError for ParseError {
+    fn position(&self) -> Option<Position> {
+        match self {
+            ParseError::MissingSection { .. } => None,
+            ParseError::UnexpectedToken { position, .. } => Some(*position),
+            ParseError::InvalidStack { position, .. } => Some(*position),
+            ParseError::SameStack { position } => Some(*position),
+            ParseError::NotEnoughCrates { position, .. } => Some(*position),
+        }
+    }
+}
@@ -0,0 +1,355 @@
+use aoc_core::{Answer, LocatedError, Solution};
+use aoc_input::{Input, Line, Position};
+use backtrace::Backtrace;
+
+mod error;
+
+pub use error::ParseError;
+
+#[derive(Debug, Clone)]
+pub struct Stacks {
+    stack: Vec<Vec<char>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Move {
+    count: u32,
+    from: u32,
+    to: u32,
+}
+
+impl Move {
+    fn new(count: u32, from: u32, to: u32) -> Move {
+        assert!(from != to); // making sure this is not null move
+        Move { count, from, to }
+    }
+}
+
+impl Stacks {
+    fn parse_indices(line: &Line) -> Result<u32, ParseError> {
+        let indices = line.text.split(' ').rfind(|s| !s.is_empty());
+
+        match indices {
+            Some(index) => index.parse().map_err(|_| ParseError::UnexpectedToken {
+                position: line.position_of(index),
+                found: index.to_owned(),
+                expected: "a stack number",
+            }),
+            None => Err(ParseError::UnexpectedToken {
+                position: line.start(),
+                found: line.text.to_owned(),
+                expected: "the stack numbers",
+            }),
+        }
+    }
+
+    fn new(size: u32) -> Stacks {
+        let casted_size = usize::try_from(size).unwrap();
+        log::debug!("Creating {casted_size} stacks.");
+        Stacks {
+            stack: std::iter::repeat_n(Vec::new(), casted_size).collect(),
+        }
+    }
+
+    fn parse_stack_line(&mut self, line: &Line) -> Result<(), ParseError> {
+        let chars: Vec<char> = line.text.chars().collect();
+        let unexpected = |cursor: usize, expected| ParseError::UnexpectedToken {
+            position: Position {
+                line: line.number,
+                column: cursor + 1,
+            },
+            found: chars.get(cursor).map(char::to_string).unwrap_or_default(),
+            expected,
+        };
+
+        let mut cursor: usize = 0;
+        for s in self.stack.iter_mut() {
+            match chars.get(cursor) {
+                Some('[') => match chars.get(cursor + 1) {
+                    Some(c) if c.is_alphabetic() => {
+                        if chars.get(cursor + 2) != Some(&']') {
+                            return Err(unexpected(cursor + 2, "']'"));
+                        }
+                        s.push(*c)
+                    }
+                    _ => return Err(unexpected(cursor + 1, "a crate")),
+                },
+                // this is empty spot, we should do nothing and skip it.
+                // Editors like to strip the trailing spaces, so the end of the line is empty as well.
+                Some(' ') | None => {}
+                Some(_) => return Err(unexpected(cursor, "'[' or a space")),
+            }
+            cursor += 4; // on space and 4
+        }
+
+        Ok(())
+    }
+
+    fn deserialize(lines: &[Line]) -> Result<Stacks, Vec<ParseError>> {
+        let l = &lines.len();
+        let indices_lines = &lines[l - 1];
+        let stack_size = Stacks::parse_indices(indices_lines).map_err(|e| vec![e])?;
+
+        let mut stacks = Stacks::new(stack_size);
+
+        let mut errors = Vec::new();
+        for l in lines.iter().take(l - 1).rev() {
+            if let Err(e) = stacks.parse_stack_line(l) {
+                errors.push(e);
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(stacks)
+        } else {
+            Err(errors)
+        }
+    }
+
+    fn pop(&mut self, count: u32, from: u32) -> Vec<char> {
+        let source_stack = self
+            .stack
+            .get_mut(usize::try_from(from).unwrap())
+            .expect("invalid source on move");
+
+        let vals: Vec<char> = (0..count).flat_map(|_i| source_stack.pop()).collect();
+        if vals.len() != usize::try_from(count).expect("can't convert count") {
+            panic!("Not enough stuff on stack {from} -> {count} needed but only {} avialable. \n backtrace={:?}", vals.len(), Backtrace::new());
+        }
+
+        vals
+    }
+
+    fn apply_move(&mut self, m: Move, reverse: bool) -> bool {
+        let vals = self.pop(m.count, m.from);
+
+        let maybe_target_stack = self.stack.get_mut(usize::try_from(m.to).unwrap());
+        if let Some(target_stack) = maybe_target_stack {
+            // We have to convert to vector, as a normal and reverse iterator have different type.
+            // Same problem as with C++ ranges.
+            let vals = if !reverse {
+                vals
+            } else {
+                vals.into_iter().rev().collect()
+            };
+            for val in vals {
+                target_stack.push(val);
+            }
+            return true;
+        }
+
+        false
+    }
+
+    fn tops(&self) -> String {
+        let tops = self.stack.iter().map(|s| s.iter().last());
+        tops.fold("".to_owned(), |mut buf, new| {
+            buf.push(*new.unwrap_or(&'?'));
+            buf
+        })
+    }
+}
+
+// The numbers are returned as they are in the file, so starting at 1.
+fn parse_move(line: &Line) -> Result<(u32, u32, u32), ParseError> {
+    // example: "move 1 from 9 to 4"
+    let tokes: Vec<&str> = line.text.split(' ').collect();
+
+    let expect_keyword = |index: usize, keyword: &'static str| match tokes.get(index) {
+        Some(t) if *t == keyword => Ok(()),
+        Some(t) => Err(ParseError::UnexpectedToken {
+            position: line.position_of(t),
+            found: t.to_string(),
+            expected: keyword,
+        }),
+        None => Err(ParseError::UnexpectedToken {
+            position: line.start(),
+            found: line.text.to_owned(),
+            expected: keyword,
+        }),
+    };
+    let expect_number = |index: usize| match tokes.get(index) {
+        Some(t) => t.parse::<u32>().map_err(|_| ParseError::UnexpectedToken {
+            position: line.position_of(t),
+            found: t.to_string(),
+            expected: "a number",
+        }),
+        None => Err(ParseError::UnexpectedToken {
+            position: line.start(),
+            found: line.text.to_owned(),
+            expected: "a number",
+        }),
+    };
+
+    // if the syntax is correct we find
+    expect_keyword(0, "move")?;
+    let count = expect_number(1)?;
+
+    // from -> index 3
+    expect_keyword(2, "from")?;
+    let from = expect_number(3)?;
+
+    // to -> index 5
+    expect_keyword(4, "to")?;
+    let to = expect_number(5)?;
+
+    if let Some(t) = tokes.get(6) {
+        return Err(ParseError::UnexpectedToken {
+            position: line.position_of(t),
+            found: t.to_string(),
+            expected: "the end of the line",
+        });
+    }
+
+    Ok((count, from, to))
+}
+
+// Checks the moves against the stacks before they are applied, only the heights
+// of the stacks are needed for this.
+fn validate_moves(
+    stacks: &Stacks,
+    parsed_moves: &[(&Line, (u32, u32, u32))],
+) -> Result<Vec<Move>, Vec<ParseError>> {
+    let stack_count = stacks.stack.len();
+    let mut heights: Vec<usize> = stacks.stack.iter().map(Vec::len).collect();
+    let mut moves = Vec::new();
+    let mut errors = Vec::new();
+
+    for (line, (count, from, to)) in parsed_moves {
+        let invalid_stack = [*from, *to]
+            .into_iter()
+            .find(|s| *s == 0 || *s as usize > stack_count);
+        if let Some(found) = invalid_stack {
+            errors.push(ParseError::InvalidStack {
+                position: line.start(),
+                found,
+                stack_count,
+            });
+            continue;
+        }
+        if from == to {
+            errors.push(ParseError::SameStack {
+                position: line.start(),
+            });
+            continue;
+        }
+
+        // Substract the id's by 1, as the index of the first element is
+        // zero in rust.
+        let (from, to) = (*from as usize - 1, *to as usize - 1);
+        if heights[from] < *count as usize {
+            errors.push(ParseError::NotEnoughCrates {
+                position: line.start(),
+                needed: *count,
+                available: heights[from],
+            });
+            // Keep going with the crates that are there, so later moves are checked as well.
+            heights[to] += heights[from];
+            heights[from] = 0;
+            continue;
+        }
+        heights[from] -= *count as usize;
+        heights[to] += *count as usize;
+
+        moves.push(Move::new(*count, from as u32, to as u32));
+    }
+
+    if errors.is_empty() {
+        Ok(moves)
+    } else {
+        Err(errors)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Puzzle {
+    stacks: Stacks,
+    moves: Vec<Move>,
+}
+
+impl Puzzle {
+    fn rearrange(&self, reverse: bool) -> String {
+        let mut stacks = self.stacks.clone();
+        for m in self.moves.iter() {
+            log::trace!("Applying {m:?} on {stacks:?}");
+            stacks.apply_move(m.clone(), reverse);
+        }
+
+        stacks.tops()
+    }
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = Puzzle;
+    type Error = ParseError;
+
+    fn parse(input: &Input) -> Result<Puzzle, Vec<ParseError>> {
+        let groups = input.groups();
+        if groups.len() != 2 {
+            return Err(vec![ParseError::MissingSection {
+                found: groups.len(),
+            }]);
+        }
+
+        let stacks = Stacks::deserialize(&groups[0]);
+
+        let mut parsed_moves = Vec::new();
+        let mut errors = Vec::new();
+        for l in groups[1].iter() {
+            match parse_move(l) {
+                Ok(m) => parsed_moves.push((l, m)),
+                Err(e) => errors.push(e),
+            }
+        }
+
+        let stacks = match stacks {
+            Ok(stacks) => stacks,
+            Err(mut stack_errors) => {
+                stack_errors.append(&mut errors);
+                return Err(stack_errors);
+            }
+        };
+
+        // The moves that could be parsed are still checked, to report as much as possible.
+        match validate_moves(&stacks, &parsed_moves) {
+            Ok(moves) if errors.is_empty() => Ok(Puzzle { stacks, moves }),
+            Ok(_) => Err(errors),
+            Err(mut move_errors) => {
+                errors.append(&mut move_errors);
+                errors.sort_by_key(|e| e.position().map(|p| p.line));
+                Err(errors)
+            }
+        }
+    }
+
+    fn part1(puzzle: &Puzzle) -> Answer {
+        Answer::from(puzzle.rearrange(false))
+    }
+
+    fn part2(puzzle: &Puzzle) -> Answer {
+        Answer::from(puzzle.rearrange(true))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn test_example_part1() {
+        assert_eq!(
+            Day5::part1(&aoc_core::example::<Day5>(env!("CARGO_MANIFEST_DIR"))),
+            Answer::from("CMZ")
+        );
+    }
+
+    #[test]
+    fn test_example_part2() {
+        assert_eq!(
+            Day5::part2(&aoc_core::example::<Day5>(env!("CARGO_MANIFEST_DIR"))),
+            Answer::from("MCD")
+        );
+    }
+}
@@ -0,0 +1,56 @@
+use std::{error, fmt};
+
+use aoc_core::LocatedError;
+use aoc_input::Position;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    Empty,
+    TooManyLines {
+        position: Position,
+    },
+    InvalidCharacter {
+        position: Position,
+        found: char,
+    },
+    NoMarker {
+        position: Position,
+        window_size: usize,
+    },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Empty => write!(f, "expected a signal, found an empty input"),
+            ParseError::TooManyLines { .. } => {
+                write!(
+                    f,
+                    "expected the signal on a single line, found another line"
+                )
+            }
+            ParseError::InvalidCharacter { found, .. } => {
+                write!(f, "expected a lowercase letter, found '{found}'")
+            }
+            ParseError::NoMarker { window_size, .. } => {
+                write!(
+                    f,
+                    "expected {window_size} different characters in a row, found none"
+                )
+            }
+        }
+    }
+}
+
+impl error::Error for ParseError {}
+
+impl LocatedError for ParseError {
+    fn position(&self) -> Option<Position> {
+        match self {
+            ParseError::Empty => None,
+            ParseError::TooManyLines { position } => Some(*position),
+            ParseError::InvalidCharacter { position, .. } => Some(*position),
+            ParseError::NoMarker { position, .. } => Some(*position),
+        }
+    }
+}
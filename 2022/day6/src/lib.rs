@@ -0,0 +1,181 @@
+use aoc_core::{Answer, Solution};
+use aoc_input::{ErrorKind, Input, Position};
+
+mod error;
+
+pub use error::ParseError;
+
+const PACKET_WINDOW: usize = 4;
+const MESSAGE_WINDOW: usize = 14;
+
+fn contains_duplicate(window: &[char]) -> bool {
+    for (i, c) in window.iter().enumerate() {
+        let duplicate = window.iter().skip(i + 1).find(|x| x == &c);
+        if let Some(_a) = duplicate {
+            return true;
+        }
+    }
+
+    false
+}
+
+// Returns the number of characters processed before the first window
+// without duplicates is complete, None when the line is shorter than a window.
+pub fn find_first_valid_window(line: &str, window_size: usize) -> Option<usize> {
+    if window_size == 0 {
+        return Some(0);
+    }
+    let chars: Vec<char> = line.chars().collect();
+    for (i, window) in chars.windows(window_size).enumerate() {
+        if !contains_duplicate(window) {
+            log::debug!(
+                "found no duplicate in {:?} at i:{}",
+                window,
+                i + window_size
+            );
+            return Some(i + window_size);
+        }
+        log::trace!("found duplicate in {:?} at i:{}", window, i + window_size);
+    }
+
+    None
+}
+
+// Where the first start-of-packet and start-of-message markers end, both are
+// searched for once while parsing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Markers {
+    pub packet: usize,
+    pub message: usize,
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = Markers;
+    type Error = ParseError;
+
+    fn parse(input: &Input) -> Result<Markers, Vec<ParseError>> {
+        let line = input.single_line().map_err(|e| {
+            let error = match (e.kind, e.line) {
+                (ErrorKind::TooManyLines, Some(line)) => ParseError::TooManyLines {
+                    position: Position { line, column: 1 },
+                },
+                _ => ParseError::Empty,
+            };
+            vec![error]
+        })?;
+        if line.text.is_empty() {
+            return Err(vec![ParseError::Empty]);
+        }
+
+        let errors: Vec<ParseError> = line
+            .text
+            .chars()
+            .enumerate()
+            .filter(|(_, c)| !c.is_ascii_lowercase())
+            .map(|(column, c)| ParseError::InvalidCharacter {
+                position: Position {
+                    line: line.number,
+                    column: column + 1,
+                },
+                found: c,
+            })
+            .collect();
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        // both parts need a marker
+        let marker = |window_size| {
+            find_first_valid_window(line.text, window_size).ok_or(ParseError::NoMarker {
+                position: line.start(),
+                window_size,
+            })
+        };
+        match (marker(PACKET_WINDOW), marker(MESSAGE_WINDOW)) {
+            (Ok(packet), Ok(message)) => Ok(Markers { packet, message }),
+            (packet, message) => Err(packet.err().into_iter().chain(message.err()).collect()),
+        }
+    }
+
+    fn part1(markers: &Markers) -> Answer {
+        Answer::from(markers.packet)
+    }
+
+    fn part2(markers: &Markers) -> Answer {
+        Answer::from(markers.message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn test_short_line() {
+        assert_eq!(find_first_valid_window("abc", 4), None);
+        assert_eq!(find_first_valid_window("abcd", 4), Some(4));
+        assert_eq!(find_first_valid_window("", 4), None);
+
+        // the windows count chars, not bytes
+        assert_eq!(find_first_valid_window("ééééa", 4), None);
+        assert_eq!(find_first_valid_window("éabc", 4), Some(4));
+    }
+
+    #[test]
+    fn test_empty_line() {
+        let input = Input::from_string("test", "\n");
+        assert_eq!(Day6::parse(&input), Err(vec![ParseError::Empty]));
+    }
+
+    #[test]
+    fn test_no_marker() {
+        let input = Input::from_string("test", "aaaaaaa\n");
+        assert_eq!(
+            Day6::parse(&input),
+            Err(vec![
+                ParseError::NoMarker {
+                    position: Position { line: 1, column: 1 },
+                    window_size: 4,
+                },
+                ParseError::NoMarker {
+                    position: Position { line: 1, column: 1 },
+                    window_size: 14,
+                },
+            ])
+        );
+
+        // a packet marker but no message marker
+        let input = Input::from_string("test", "abcd\n");
+        assert_eq!(Day6::parse(&input).unwrap_err().len(), 1);
+    }
+
+    #[test]
+    fn test_markers() {
+        assert_eq!(
+            aoc_core::example::<Day6>(env!("CARGO_MANIFEST_DIR")),
+            Markers {
+                packet: 7,
+                message: 19
+            }
+        );
+    }
+
+    #[test]
+    fn test_example_part1() {
+        assert_eq!(
+            Day6::part1(&aoc_core::example::<Day6>(env!("CARGO_MANIFEST_DIR"))),
+            Answer::from(7)
+        );
+    }
+
+    #[test]
+    fn test_example_part2() {
+        assert_eq!(
+            Day6::part2(&aoc_core::example::<Day6>(env!("CARGO_MANIFEST_DIR"))),
+            Answer::from(19)
+        );
+    }
+}
@@ -1,50 +1,16 @@
-use std::{
-    fs::File,
-    io::{self, BufRead, BufReader, Lines},
-    path::Path,
-};
+use std::path::Path;
 
-fn read_lines(filename: &std::path::Path) -> io::Result<Lines<BufReader<File>>> {
-    let file = File::open(filename)?;
-    let reader = BufReader::new(file);
-    let lines = reader.lines();
-    Ok(lines)
-}
-
-fn contains_duplicate(window: &Vec<char>) -> bool {
-    for (i, c) in window.iter().enumerate() {
-        let duplicate = window.iter().skip(i + 1).find(|x| x == &c);
-        if let Some(_a) = duplicate {
-            return true;
-        }
-    }
-
-    false
-}
-
-fn find_first_valid_window(line: &String, window_size: usize)
-{
-    for i in 0..line.len() - window_size {
-        let window: Vec<char> = line.chars().skip(i).take(window_size).collect();
-        if !contains_duplicate(&window) {
-            println!("found no duplicate it {:?} at i:{}", &window, i + window_size);
-            break;
-        }
-        else{
-            println!("found duplicate it {:?} at i:{}", &window, i + window_size);
-        }
-    }
-}
+use aoc_core::{init_logging, parse_or_exit, read_or_exit, Solution};
+use day6::Day6;
 
 fn main() {
-    let p = Path::new("input.txt");
-    let line = read_lines(p)
-        .expect("unable to read input file input.txt")
-        .nth(0)
-        // We ignore io errors here, I also like to live dangerously.
-        .unwrap()
-        .unwrap();
+    init_logging(0);
+    let input = read_or_exit(Path::new("input.txt"));
+    let markers = parse_or_exit::<Day6>(&input);
 
-    // find_first_valid_window(&line, 4);
-    find_first_valid_window(&line, 14);
+    println!("part1: start-of-packet marker at {}", Day6::part1(&markers));
+    println!(
+        "part2: start-of-message marker at {}",
+        Day6::part2(&markers)
+    );
 }
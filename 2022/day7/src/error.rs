@@ -0,0 +1,79 @@
+use std::{error, fmt};
+
+use aoc_core::LocatedError;
+use aoc_input::Position;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    EmptyLine {
+        position: Position,
+    },
+    UnexpectedLine {
+        position: Position,
+        found: String,
+    },
+    UnknownCommand {
+        position: Position,
+        found: String,
+    },
+    MissingArgument {
+        position: Position,
+        expected: &'static str,
+    },
+    InvalidSize {
+        position: Position,
+        found: String,
+    },
+    UnknownDirectory {
+        position: Position,
+        found: String,
+    },
+    ListingWithoutLs {
+        position: Position,
+    },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::EmptyLine { .. } => {
+                write!(f, "expected a command or a listing, found an empty line")
+            }
+            ParseError::UnexpectedLine { found, .. } => write!(
+                f,
+                "expected a command, a directory or a file, found '{found}'"
+            ),
+            ParseError::UnknownCommand { found, .. } => {
+                write!(f, "expected the command cd or ls, found '{found}'")
+            }
+            ParseError::MissingArgument { expected, .. } => {
+                write!(f, "expected {expected}, found the end of the line")
+            }
+            ParseError::InvalidSize { found, .. } => {
+                write!(f, "expected a file size, found '{found}'")
+            }
+            ParseError::UnknownDirectory { found, .. } => {
+                write!(f, "expected an existing subdirectory, found '{found}'")
+            }
+            ParseError::ListingWithoutLs { .. } => {
+                write!(f, "expected ls before listing directories and files")
+            }
+        }
+    }
+}
+
+impl error::Error for ParseError {}
+
+impl LocatedError for ParseError {
+    fn position(&self) -> Option<Position> {
+        match self {
+            ParseError::EmptyLine { position }
+            | ParseError::UnexpectedLine { position, .. }
+            | ParseError::UnknownCommand { position, .. }
+            | ParseError::MissingArgument { position, .. }
+            | ParseError::InvalidSize { position, .. }
+            | ParseError::UnknownDirectory { position, .. }
+            | ParseError::ListingWithoutLs { position } => Some(*position),
+        }
+    }
+}
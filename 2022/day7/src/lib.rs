@@ -0,0 +1,530 @@
+use aoc_core::{Answer, Solution};
+use aoc_input::{Input, Line, Position};
+
+mod error;
+
+pub use error::ParseError;
+
+pub trait Sizable {
+    fn size(&self) -> i32;
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct File {
+    name: String,
+    size: i32,
+}
+
+impl File {
+    fn new(name: String, size: i32) -> File {
+        File { name, size }
+    }
+}
+
+impl Sizable for File {
+    fn size(&self) -> i32 {
+        self.size
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Directory {
+    name: String,
+    subdirs: Vec<Directory>,
+    files: Vec<File>,
+}
+
+impl Directory {
+    fn new(name: String) -> Directory {
+        Directory {
+            name,
+            subdirs: Vec::new(),
+            files: Vec::new(),
+        }
+    }
+}
+
+fn total_size<T>(elements: &[T]) -> i32
+where
+    T: Sizable,
+{
+    elements
+        .iter()
+        .map(|x| x.size())
+        .reduce(|x, y| x + y)
+        .unwrap_or(0)
+}
+
+impl Sizable for Directory {
+    fn size(&self) -> i32 {
+        total_size(&self.files) + total_size(&self.subdirs)
+    }
+}
+
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, PartialEq, Eq)]
+enum Token<'a> {
+    CD(&'a str), // location to chance dir to
+    LS(),
+    DIR(&'a str),       // name
+    FILE(&'a str, i32), // name, size
+}
+
+fn missing_argument(line: &Line, expected: &'static str) -> ParseError {
+    ParseError::MissingArgument {
+        position: Position {
+            line: line.number,
+            column: line.text.chars().count() + 1,
+        },
+        expected,
+    }
+}
+
+fn parse_dir<'a>(line: &Line<'a>) -> Result<Token<'a>, ParseError> {
+    let mut words = line.text.split(' ').skip(1);
+    match words.next() {
+        Some(name) if !name.is_empty() => Ok(Token::DIR(name)),
+        _ => Err(missing_argument(line, "a directory name")),
+    }
+}
+
+fn parse_file<'a>(line: &Line<'a>) -> Result<Token<'a>, ParseError> {
+    let mut words = line.text.split(' ');
+
+    let size = words.next().unwrap_or_default();
+    let size: i32 = size.parse().map_err(|_| ParseError::InvalidSize {
+        position: line.position_of(size),
+        found: size.to_owned(),
+    })?;
+    match words.next() {
+        Some(name) if !name.is_empty() => Ok(Token::FILE(name, size)),
+        _ => Err(missing_argument(line, "a file name")),
+    }
+}
+
+fn parse_command<'a>(line: &Line<'a>) -> Result<Token<'a>, ParseError> {
+    let mut words = line.text.split(' ').skip(1);
+    match words.next() {
+        Some("ls") => Ok(Token::LS()),
+        Some("cd") => match words.next() {
+            Some(cd_location) if !cd_location.is_empty() => Ok(Token::CD(cd_location)),
+            _ => Err(missing_argument(line, "a directory to change to")),
+        },
+        Some(command) if !command.is_empty() => Err(ParseError::UnknownCommand {
+            position: line.position_of(command),
+            found: command.to_owned(),
+        }),
+        _ => Err(missing_argument(line, "a command")),
+    }
+}
+
+fn parse_line<'a>(line: &Line<'a>) -> Result<Token<'a>, ParseError> {
+    match line.text.chars().next() {
+        Some('d') if line.text.starts_with("dir ") => parse_dir(line),
+        Some('$') => parse_command(line),
+        Some('1'..='9') => parse_file(line),
+        Some(_) => Err(ParseError::UnexpectedLine {
+            position: line.start(),
+            found: line.text.to_owned(),
+        }),
+        None => Err(ParseError::EmptyLine {
+            position: line.start(),
+        }),
+    }
+}
+
+#[derive(Debug)]
+pub struct FileSystem {
+    dirs: Vec<Directory>,
+}
+
+impl FileSystem {
+    fn new() -> FileSystem {
+        let root = Directory::new("/".to_owned());
+        FileSystem { dirs: vec![root] }
+    }
+
+    fn change_dir_up(&mut self) {
+        assert!(!self.dirs.is_empty());
+        if let Some(child) = self.dirs.pop() {
+            // We are already at the root, popping off a second
+            // element will have no element. Makes sense as we
+            // should do nothing.
+            if let Some(mut current_dir) = self.dirs.pop() {
+                current_dir.subdirs.push(child);
+                self.dirs.push(current_dir); // put the current dir back
+            }
+        }
+    }
+
+    fn change_dir_root(&mut self) {
+        assert!(!self.dirs.is_empty());
+        // Keep going up untill everything is back into 1 node.
+        while self.dirs.len() != 1 {
+            self.change_dir_up();
+        }
+    }
+
+    // Returns false if there is no subfolder with that name.
+    fn change_dir(&mut self, name: &str) -> bool {
+        if name == ".." {
+            self.change_dir_up();
+        } else if name == "/" {
+            self.change_dir_root();
+        } else {
+            // remove the dir from the list of dirs
+            if let Some(mut current_dir) = self.dirs.pop() {
+                if let Some(new_current_dir_index) = current_dir
+                    .subdirs
+                    .iter()
+                    .enumerate()
+                    .find(|(_i, dir)| dir.name == name)
+                    .map(|(i, _dir)| i)
+                {
+                    let new_change_dir = current_dir.subdirs.remove(new_current_dir_index);
+
+                    self.dirs.push(current_dir);
+                    self.dirs.push(new_change_dir);
+                } else {
+                    // Put the current dir back, we stay where we are.
+                    self.dirs.push(current_dir);
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+
+    // add directory to current dir
+    fn push_directory(&mut self, name: &str) {
+        let new_dir = Directory::new(name.to_owned());
+        if let Some(current_dir) = self.dirs.iter_mut().last() {
+            current_dir.subdirs.push(new_dir);
+        }
+    }
+
+    // add file to current directory
+    fn push_file(&mut self, name: &str, size: i32) {
+        let new_file = File::new(name.to_owned(), size);
+        if let Some(current_dir) = self.dirs.iter_mut().last() {
+            current_dir.files.push(new_file);
+        }
+    }
+}
+
+struct Parser<'a> {
+    fs: FileSystem,
+    tokens: Vec<(Position, Token<'a>)>,
+    errors: Vec<ParseError>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(tokens: Vec<(Position, Token<'a>)>) -> Parser<'a> {
+        let mut tokens = tokens;
+        tokens.reverse();
+
+        Parser {
+            fs: FileSystem::new(),
+            tokens,
+            errors: Vec::new(),
+        }
+    }
+
+    fn next(&mut self) -> Option<(Position, Token<'a>)> {
+        self.tokens.pop()
+    }
+
+    fn take_files_or_dirs(&mut self) {
+        // Stops at the end of the file.
+        while let Some((position, t)) = self.next() {
+            match t {
+                Token::DIR(name) => {
+                    self.fs.push_directory(name);
+                }
+                Token::FILE(name, size) => {
+                    self.fs.push_file(name, size);
+                }
+                _ => {
+                    // Not the correct kind of toke, put it back.
+                    self.tokens.push((position, t));
+                    break;
+                }
+            }
+        }
+    }
+
+    // Keeps going after an error, so all of them are reported.
+    fn parse(&mut self) {
+        while let Some((position, token)) = self.next() {
+            match token {
+                Token::CD(name) => {
+                    if !self.fs.change_dir(name) {
+                        self.errors.push(ParseError::UnknownDirectory {
+                            position,
+                            found: name.to_owned(),
+                        });
+                    }
+                }
+                Token::LS() => self.take_files_or_dirs(),
+                _ => self.errors.push(ParseError::ListingWithoutLs { position }),
+            }
+        }
+
+        self.fs.change_dir_root();
+    }
+}
+
+pub trait MutableDirectoryVisitor {
+    // Returns true if continue, false if stop.
+    fn visit(&mut self, dir: &Directory);
+}
+
+struct DirectoryCounter {
+    count: i32,
+}
+
+impl MutableDirectoryVisitor for DirectoryCounter {
+    fn visit(&mut self, _dir: &Directory) {
+        self.count += 1;
+    }
+}
+
+pub fn count_dirs(fs: &FileSystem) -> i32 {
+    let mut vis = DirectoryCounter { count: 0 };
+    visit_mutable_directory_visitor(fs, &mut vis);
+
+    vis.count
+}
+
+pub fn visit_mutable_directory_visitor<TVisitor>(fs: &FileSystem, vis: &mut TVisitor)
+where
+    TVisitor: MutableDirectoryVisitor,
+{
+    let mut visits: Vec<&Directory> = Vec::new();
+
+    for f in &fs.dirs {
+        visits.push(f);
+    }
+
+    while let Some(top_el) = visits.pop() {
+        for f in &top_el.subdirs {
+            visits.push(f);
+        }
+        vis.visit(top_el);
+    }
+}
+
+#[derive(Debug)]
+pub struct Part1Counter {
+    pub number_of_files: i32,
+    pub total_size: i32,
+    pub dir_names: Vec<String>,
+}
+
+impl Part1Counter {
+    fn new() -> Part1Counter {
+        Part1Counter {
+            number_of_files: 0,
+            total_size: 0,
+            dir_names: Vec::new(),
+        }
+    }
+}
+
+impl MutableDirectoryVisitor for Part1Counter {
+    fn visit(&mut self, dir: &Directory) {
+        let s = dir.size();
+        log::debug!("Analyzing directory name={:?} size={:?}.", &dir.name, &s);
+        if s <= 100000 {
+            // "at most"
+            self.number_of_files += 1;
+            self.total_size += s;
+            self.dir_names.push(dir.name.clone());
+        }
+    }
+}
+
+struct Part2Vis {
+    required_free_size: i32,
+    solution: Option<(String, i32)>,
+}
+
+impl MutableDirectoryVisitor for Part2Vis {
+    fn visit(&mut self, dir: &Directory) {
+        let s = dir.size();
+        if s >= self.required_free_size {
+            if let Some((_, current_solution_size)) = self.solution {
+                if s < current_solution_size {
+                    // Only accept better fitting solutions.
+                    self.solution = Some((dir.name.clone(), s));
+                }
+            } else {
+                // No solution was found so far, so use the first fitting one.
+                self.solution = Some((dir.name.clone(), s));
+            }
+        }
+    }
+}
+
+pub struct DirsVistor {
+    pub dirs: Vec<String>,
+}
+
+impl MutableDirectoryVisitor for DirsVistor {
+    fn visit(&mut self, dir: &Directory) {
+        self.dirs.push(dir.name.clone());
+    }
+}
+
+const DISK_SIZE: i32 = 70000000;
+const REQUIRED_UNUSED_SIZE: i32 = 30000000;
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = FileSystem;
+    type Error = ParseError;
+
+    fn parse(input: &Input) -> Result<FileSystem, Vec<ParseError>> {
+        // step1: read out the file, and populate the tree structure
+        let mut tokens = Vec::new();
+        let mut errors = Vec::new();
+        for line in input.lines() {
+            match parse_line(&line) {
+                Ok(token) => tokens.push((line.start(), token)),
+                Err(e) => errors.push(e),
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        let mut p = Parser::new(tokens);
+        p.parse();
+        if !p.errors.is_empty() {
+            return Err(p.errors);
+        }
+
+        Ok(p.fs)
+    }
+
+    fn part1(fs: &FileSystem) -> Answer {
+        // step2: To begin, find all of the directories with a total size of at most 100000,
+        // then calculate the sum of their total sizes.
+        let mut part1_visitor = Part1Counter::new();
+        visit_mutable_directory_visitor(fs, &mut part1_visitor);
+        Answer::from(part1_visitor.total_size)
+    }
+
+    fn part2(fs: &FileSystem) -> Answer {
+        let used_size = total_size(&fs.dirs);
+        let mut part2_visitor = Part2Vis {
+            required_free_size: REQUIRED_UNUSED_SIZE - (DISK_SIZE - used_size),
+            solution: None,
+        };
+        visit_mutable_directory_visitor(fs, &mut part2_visitor);
+        let (_name, size) = part2_visitor
+            .solution
+            .expect("no directory is large enough to free up the required space");
+        Answer::from(size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parse_command, parse_dir, parse_file, Day7, FileSystem, ParseError, Token};
+    use aoc_core::{Answer, Solution};
+    use aoc_input::{Line, Position};
+
+    #[test]
+    fn test_dir_parse() {
+        let input = Line {
+            number: 1,
+            text: "dir qcznqph",
+        };
+        let res_dir_token = parse_dir(&input);
+
+        let expected_dir_name = "qcznqph";
+        let expected_dir_token = Token::DIR(expected_dir_name);
+
+        assert!(res_dir_token == Ok(expected_dir_token));
+    }
+
+    #[test]
+    fn test_file_parse() {
+        let input = Line {
+            number: 1,
+            text: "184686 jzn",
+        };
+        let res_dir_token = parse_file(&input);
+
+        let expected_file_name = "jzn";
+        let expected_file_size = 184686;
+        let expected_dir_token = Token::FILE(expected_file_name, expected_file_size);
+
+        assert!(res_dir_token == Ok(expected_dir_token));
+    }
+
+    #[test]
+    fn test_command_parse_error() {
+        let input = Line {
+            number: 4,
+            text: "$ rm -rf",
+        };
+        let expected_error = ParseError::UnknownCommand {
+            position: Position { line: 4, column: 3 },
+            found: "rm".to_owned(),
+        };
+
+        assert_eq!(parse_command(&input), Err(expected_error));
+    }
+
+    #[test]
+    fn test_filesystem() {
+        let mut fs = FileSystem::new();
+        fs.push_file("file1", 1);
+        fs.push_directory("dir1");
+        fs.push_directory("dir2");
+        fs.change_dir("dir2");
+        fs.push_file("dir2_file1", 3);
+        fs.push_file("dir2_file2", 4);
+
+        fs.change_dir_up();
+        assert!(!fs.change_dir("dir2_file1"));
+
+        fs.change_dir("/");
+
+        assert!(fs.dirs.len() == 1);
+        let root = fs.dirs.iter().last().unwrap();
+        assert!(root.name == "/");
+        assert!(root.subdirs.len() == 2);
+        let dir1 = root.subdirs.first().unwrap();
+        assert!(dir1.name == "dir1");
+        let dir2 = root.subdirs.get(1).unwrap();
+        assert!(dir2.name == "dir2");
+
+        let dir2_file1 = dir2.files.first().unwrap();
+        assert!(dir2_file1.name == "dir2_file1");
+        assert!(dir2_file1.size == 3);
+        let dir2_file2 = dir2.files.get(1).unwrap();
+        assert!(dir2_file2.name == "dir2_file2");
+        assert!(dir2_file2.size == 4);
+    }
+
+    #[test]
+    fn test_example_part1() {
+        assert_eq!(
+            Day7::part1(&aoc_core::example::<Day7>(env!("CARGO_MANIFEST_DIR"))),
+            Answer::from(95437)
+        );
+    }
+
+    #[test]
+    fn test_example_part2() {
+        assert_eq!(
+            Day7::part2(&aoc_core::example::<Day7>(env!("CARGO_MANIFEST_DIR"))),
+            Answer::from(24933642)
+        );
+    }
+}
@@ -0,0 +1,148 @@
+use std::any::Any;
+use std::error::Error;
+use std::fmt;
+use std::path::Path;
+
+use aoc_input::{Input, Position};
+
+mod answer;
+
+pub use answer::Answer;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(self) -> u32 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+
+    pub fn from_number(n: u32) -> Option<Part> {
+        match n {
+            1 => Some(Part::One),
+            2 => Some(Part::Two),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+// Errors found while parsing, the position is printed in front of the message.
+pub trait LocatedError: Error {
+    fn position(&self) -> Option<Position>;
+}
+
+// Formats the error as "file:line:column: message".
+pub fn format_error(input: &Input, e: &dyn LocatedError) -> String {
+    format_error_in(input.name(), e)
+}
+
+// Same as format_error, for input that is streamed instead of read into an Input.
+pub fn format_error_in(source_name: &str, e: &dyn LocatedError) -> String {
+    match e.position() {
+        Some(position) => format!("{source_name}:{position}: {e}"),
+        None => format!("{source_name}: {e}"),
+    }
+}
+
+// Logs go to stderr, RUST_LOG overrides the level picked by the number of -v flags.
+// Without any flags only warnings and errors are shown.
+pub fn init_logging(verbose: u8) {
+    let level = match verbose {
+        0 => log::LevelFilter::Warn,
+        1 => log::LevelFilter::Info,
+        2 => log::LevelFilter::Debug,
+        _ => log::LevelFilter::Trace,
+    };
+    env_logger::Builder::new()
+        .filter_level(level)
+        .parse_env("RUST_LOG")
+        .format_timestamp(None)
+        .init();
+}
+
+// For the day binaries, prints why the file can't be read and exits.
+pub fn read_or_exit(path: &Path) -> Input {
+    Input::from_file(path).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        std::process::exit(1);
+    })
+}
+
+// For the day binaries, prints every parse error and exits.
+pub fn parse_or_exit<S: Solution>(input: &Input) -> S::Input {
+    S::parse(input).unwrap_or_else(|errors| {
+        for e in &errors {
+            eprintln!("{}", format_error(input, e));
+        }
+        std::process::exit(1);
+    })
+}
+
+// For the tests of the days, parses the example.txt next to the Cargo.toml of the
+// day, pass it env!("CARGO_MANIFEST_DIR"). Panics with the parse errors.
+pub fn example<S: Solution>(manifest_dir: &str) -> S::Input {
+    let input = Input::from_file(&Path::new(manifest_dir).join("example.txt"))
+        .unwrap_or_else(|e| panic!("{e}"));
+    S::parse(&input).unwrap_or_else(|errors| {
+        let errors: Vec<String> = errors.iter().map(|e| format_error(&input, e)).collect();
+        panic!("{}", errors.join("\n"))
+    })
+}
+
+// Every day implements this, parsing is done once and shared by both parts.
+// Parsing does not stop at the first error, all errors in the input are returned.
+pub trait Solution {
+    type Input: 'static;
+    type Error: LocatedError + 'static;
+
+    fn parse(input: &Input) -> Result<Self::Input, Vec<Self::Error>>;
+    fn part1(input: &Self::Input) -> Answer;
+    fn part2(input: &Self::Input) -> Answer;
+
+    fn solve(input: &Self::Input, part: Part) -> Answer {
+        match part {
+            Part::One => Self::part1(input),
+            Part::Two => Self::part2(input),
+        }
+    }
+}
+
+// Object safe version of Solution, so days with different input types
+// can be stored next to each other (the parsed input is type erased).
+pub trait AnySolution {
+    fn parse(&self, input: &Input) -> Result<Box<dyn Any>, Vec<Box<dyn LocatedError>>>;
+    fn solve(&self, parsed: &dyn Any, part: Part) -> Answer;
+}
+
+impl<S: Solution> AnySolution for S {
+    fn parse(&self, input: &Input) -> Result<Box<dyn Any>, Vec<Box<dyn LocatedError>>> {
+        match S::parse(input) {
+            Ok(parsed) => Ok(Box::new(parsed)),
+            Err(errors) => Err(errors
+                .into_iter()
+                .map(|e| Box::new(e) as Box<dyn LocatedError>)
+                .collect()),
+        }
+    }
+
+    fn solve(&self, parsed: &dyn Any, part: Part) -> Answer {
+        let parsed = parsed
+            .downcast_ref::<S::Input>()
+            .expect("parsed input does not belong to this solution");
+        S::solve(parsed, part)
+    }
+}
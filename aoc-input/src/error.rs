@@ -0,0 +1,57 @@
+use std::{error, fmt, io};
+
+#[derive(Debug)]
+pub enum ErrorKind {
+    Io(io::Error),
+    InvalidUtf8,
+    Empty,
+    TooManyLines,
+}
+
+// Every error knows where it comes from, a file name or "<stdin>".
+#[derive(Debug)]
+pub struct InputError {
+    pub source_name: String,
+    pub line: Option<usize>,
+    pub kind: ErrorKind,
+}
+
+impl InputError {
+    pub fn new(source_name: &str, line: Option<usize>, kind: ErrorKind) -> InputError {
+        InputError {
+            source_name: source_name.to_owned(),
+            line,
+            kind,
+        }
+    }
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.source_name)?;
+        if let Some(line) = self.line {
+            write!(f, ":{line}")?;
+        }
+        write!(f, ": {}", self.kind)
+    }
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorKind::Io(e) => write!(f, "unable to read input: {e}"),
+            ErrorKind::InvalidUtf8 => write!(f, "line is not valid utf-8"),
+            ErrorKind::Empty => write!(f, "input is empty"),
+            ErrorKind::TooManyLines => write!(f, "expected a single line"),
+        }
+    }
+}
+
+impl error::Error for InputError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match &self.kind {
+            ErrorKind::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,195 @@
+use std::io::{self, BufRead};
+
+use crate::{ErrorKind, InputError, Line};
+
+// Lines with only whitespace separate groups as well, editors don't always show them.
+pub fn is_blank(text: &str) -> bool {
+    text.trim().is_empty()
+}
+
+// Removes the line ending, "\n" or "\r\n", and any trailing whitespace.
+pub fn clean_line(text: &str) -> &str {
+    text.trim_end()
+}
+
+// The rule both ways of reading groups follow: blank lines end a group, any number
+// of them can be between groups and the last group does not need one after it.
+#[derive(Debug, Default)]
+pub(crate) struct Grouping {
+    in_group: bool,
+}
+
+impl Grouping {
+    // None for a blank line, otherwise if the line starts a new group.
+    pub(crate) fn new_group(&mut self, text: &str) -> Option<bool> {
+        if is_blank(text) {
+            self.in_group = false;
+            return None;
+        }
+        let new_group = !self.in_group;
+        self.in_group = true;
+        Some(new_group)
+    }
+}
+
+// A line that was read from a stream, the text is owned because the buffer is reused.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OwnedLine {
+    pub number: usize,
+    pub text: String,
+}
+
+impl OwnedLine {
+    pub fn as_line(&self) -> Line<'_> {
+        Line {
+            number: self.number,
+            text: &self.text,
+        }
+    }
+}
+
+// A line that is not blank, new_group is set for the first line after blank lines.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroupedLine {
+    pub line: OwnedLine,
+    pub new_group: bool,
+}
+
+// Streams the lines that are not blank one at a time, so a group of any size
+// is read in constant memory. Reading stops after the first error.
+// See Input::groups for the groups of input that is already in memory.
+pub struct GroupedLines<R> {
+    source_name: String,
+    reader: R,
+    buffer: String,
+    line_number: usize,
+    grouping: Grouping,
+    done: bool,
+}
+
+impl<R: BufRead> GroupedLines<R> {
+    pub fn new(source_name: &str, reader: R) -> GroupedLines<R> {
+        GroupedLines {
+            source_name: source_name.to_owned(),
+            reader,
+            buffer: String::new(),
+            line_number: 0,
+            grouping: Grouping::default(),
+            done: false,
+        }
+    }
+
+    fn error(&mut self, e: io::Error) -> InputError {
+        self.done = true;
+        let kind = if e.kind() == io::ErrorKind::InvalidData {
+            ErrorKind::InvalidUtf8
+        } else {
+            ErrorKind::Io(e)
+        };
+        InputError::new(&self.source_name, Some(self.line_number), kind)
+    }
+}
+
+impl<R: BufRead> Iterator for GroupedLines<R> {
+    type Item = Result<GroupedLine, InputError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            self.buffer.clear();
+            self.line_number += 1;
+            match self.reader.read_line(&mut self.buffer) {
+                Ok(0) => self.done = true,
+                Ok(_) => {
+                    if let Some(new_group) = self.grouping.new_group(&self.buffer) {
+                        return Some(Ok(GroupedLine {
+                            line: OwnedLine {
+                                number: self.line_number,
+                                text: clean_line(&self.buffer).to_owned(),
+                            },
+                            new_group,
+                        }));
+                    }
+                }
+                Err(e) => return Some(Err(self.error(e))),
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::groups::*;
+
+    fn groups(text: &[u8]) -> Vec<Vec<(usize, String)>> {
+        let mut groups: Vec<Vec<(usize, String)>> = Vec::new();
+        for l in GroupedLines::new("test", text) {
+            let l = l.unwrap();
+            if l.new_group {
+                groups.push(Vec::new());
+            }
+            groups
+                .last_mut()
+                .unwrap()
+                .push((l.line.number, l.line.text));
+        }
+        groups
+    }
+
+    fn texts(text: &[u8]) -> Vec<Vec<String>> {
+        groups(text)
+            .into_iter()
+            .map(|g| g.into_iter().map(|(_, t)| t).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_final_group() {
+        assert_eq!(texts(b"1\n2\n\n3"), vec![vec!["1", "2"], vec!["3"]]);
+        assert_eq!(texts(b"1\n2\n\n3\n"), vec![vec!["1", "2"], vec!["3"]]);
+    }
+
+    #[test]
+    fn test_crlf() {
+        assert_eq!(
+            texts(b"1\r\n2\r\n\r\n3\r\n"),
+            vec![vec!["1", "2"], vec!["3"]]
+        );
+    }
+
+    #[test]
+    fn test_whitespace() {
+        assert_eq!(
+            texts(b"1 \n2\t\n \t\n3  \r\n"),
+            vec![vec!["1", "2"], vec!["3"]]
+        );
+    }
+
+    #[test]
+    fn test_multiple_blank_lines() {
+        assert_eq!(
+            groups(b"\n\n1\n\n\n\n2\n\n"),
+            vec![vec![(3, "1".to_owned())], vec![(7, "2".to_owned())]]
+        );
+        assert!(groups(b"").is_empty());
+        assert!(groups(b"\n \n\r\n").is_empty());
+    }
+
+    #[test]
+    fn test_grouped_lines() {
+        let lines: Vec<(usize, bool)> = GroupedLines::new("test", &b"1\n2\n\n \n3\n"[..])
+            .map(|l| l.unwrap())
+            .map(|l| (l.line.number, l.new_group))
+            .collect();
+        assert_eq!(lines, vec![(1, true), (2, false), (5, true)]);
+    }
+
+    #[test]
+    fn test_invalid_utf8() {
+        let mut lines = GroupedLines::new("test", &b"1\n\n\xff\n"[..]);
+        assert!(lines.next().unwrap().is_ok());
+        let e = lines.next().unwrap().unwrap_err();
+        assert_eq!(e.to_string(), "test:3: line is not valid utf-8");
+        assert!(lines.next().is_none());
+    }
+}
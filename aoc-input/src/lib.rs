@@ -0,0 +1,249 @@
+use std::fmt;
+use std::io::{self, Read};
+use std::path::Path;
+
+mod error;
+mod groups;
+
+pub use error::{ErrorKind, InputError};
+pub use groups::{clean_line, is_blank, GroupedLine, GroupedLines, OwnedLine};
+
+use groups::Grouping;
+
+// Puzzle input, read from a file, stdin or a string, together with its name
+// so errors can point to "file:line".
+#[derive(Debug, Clone)]
+pub struct Input {
+    name: String,
+    text: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Line<'a> {
+    // Line numbers start at 1, like in an editor.
+    pub number: usize,
+    pub text: &'a str,
+}
+
+// Line and column (both starting at 1) of a token in the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+impl Line<'_> {
+    pub fn start(&self) -> Position {
+        Position {
+            line: self.number,
+            column: 1,
+        }
+    }
+
+    // Position of a token that was split off from this line.
+    pub fn position_of(&self, token: &str) -> Position {
+        let offset = (token.as_ptr() as usize).wrapping_sub(self.text.as_ptr() as usize);
+        let column = match self.text.get(..offset) {
+            Some(before) => before.chars().count() + 1,
+            // Not a part of this line, point to the start of it.
+            None => 1,
+        };
+
+        Position {
+            line: self.number,
+            column,
+        }
+    }
+}
+
+// A position in the input, displayed as "file:line".
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Location<'a> {
+    pub source_name: &'a str,
+    pub line: usize,
+}
+
+impl fmt::Display for Location<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.source_name, self.line)
+    }
+}
+
+impl Input {
+    pub fn from_string(name: &str, text: impl Into<String>) -> Input {
+        Input {
+            name: name.to_owned(),
+            text: text.into(),
+        }
+    }
+
+    pub fn from_file(path: &Path) -> Result<Input, InputError> {
+        let name = path.display().to_string();
+        let bytes =
+            std::fs::read(path).map_err(|e| InputError::new(&name, None, ErrorKind::Io(e)))?;
+        Input::from_bytes(&name, bytes)
+    }
+
+    pub fn from_stdin() -> Result<Input, InputError> {
+        let name = "<stdin>";
+        let mut bytes = Vec::new();
+        io::stdin()
+            .read_to_end(&mut bytes)
+            .map_err(|e| InputError::new(name, None, ErrorKind::Io(e)))?;
+        Input::from_bytes(name, bytes)
+    }
+
+    // "-" reads from stdin, anything else is a file.
+    pub fn from_path_or_stdin(path: &Path) -> Result<Input, InputError> {
+        if path == Path::new("-") {
+            Input::from_stdin()
+        } else {
+            Input::from_file(path)
+        }
+    }
+
+    fn from_bytes(name: &str, bytes: Vec<u8>) -> Result<Input, InputError> {
+        match String::from_utf8(bytes) {
+            Ok(text) => Ok(Input::from_string(name, text)),
+            Err(e) => {
+                let valid = &e.as_bytes()[..e.utf8_error().valid_up_to()];
+                let line = valid.iter().filter(|b| **b == b'\n').count() + 1;
+                Err(InputError::new(name, Some(line), ErrorKind::InvalidUtf8))
+            }
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn location(&self, line: usize) -> Location<'_> {
+        Location {
+            source_name: &self.name,
+            line,
+        }
+    }
+
+    pub fn lines(&self) -> impl Iterator<Item = Line<'_>> {
+        self.text.lines().enumerate().map(|(i, text)| Line {
+            number: i + 1,
+            text,
+        })
+    }
+
+    // Lines grouped by the blank lines in between them, the last group
+    // does not need a blank line after it. Trailing whitespace is removed from the lines,
+    // see GroupedLines for reading the groups from a stream.
+    pub fn groups(&self) -> Vec<Vec<Line<'_>>> {
+        let mut groups: Vec<Vec<Line<'_>>> = Vec::new();
+        let mut grouping = Grouping::default();
+        for line in self.lines() {
+            let Some(new_group) = grouping.new_group(line.text) else {
+                continue;
+            };
+            if new_group {
+                groups.push(Vec::new());
+            }
+            if let Some(group) = groups.last_mut() {
+                group.push(Line {
+                    number: line.number,
+                    text: clean_line(line.text),
+                });
+            }
+        }
+
+        groups
+    }
+
+    // For puzzles where the whole input is one line, a trailing newline is allowed.
+    pub fn single_line(&self) -> Result<Line<'_>, InputError> {
+        let mut lines = self.lines();
+        let first = lines
+            .next()
+            .ok_or_else(|| InputError::new(&self.name, None, ErrorKind::Empty))?;
+        if let Some(extra) = lines.find(|l| !l.text.is_empty()) {
+            return Err(InputError::new(
+                &self.name,
+                Some(extra.number),
+                ErrorKind::TooManyLines,
+            ));
+        }
+
+        Ok(first)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn test_groups() {
+        let input = Input::from_string("test", "1\n2\n\n3\n\n\n4\n5");
+        let groups: Vec<Vec<&str>> = input
+            .groups()
+            .iter()
+            .map(|g| g.iter().map(|l| l.text).collect())
+            .collect();
+
+        assert_eq!(groups, vec![vec!["1", "2"], vec!["3"], vec!["4", "5"]]);
+        assert_eq!(input.groups()[2][0].number, 7);
+
+        let input = Input::from_string("test", "1 \r\n2\r\n \t\r\n3\r\n");
+        let groups: Vec<Vec<&str>> = input
+            .groups()
+            .iter()
+            .map(|g| g.iter().map(|l| l.text).collect())
+            .collect();
+        assert_eq!(groups, vec![vec!["1", "2"], vec!["3"]]);
+    }
+
+    #[test]
+    fn test_position_of() {
+        let line = Line {
+            number: 3,
+            text: "move 1 from 2 to 3",
+        };
+        let from = line.text.split(' ').nth(3).unwrap();
+        assert_eq!(
+            line.position_of(from),
+            Position {
+                line: 3,
+                column: 13
+            }
+        );
+        assert_eq!(line.position_of("2"), line.start());
+    }
+
+    #[test]
+    fn test_single_line() {
+        let input = Input::from_string("test", "abc\n");
+        assert_eq!(input.single_line().unwrap().text, "abc");
+
+        let input = Input::from_string("test", "abc\ndef\n");
+        let e = input.single_line().unwrap_err();
+        assert_eq!(e.to_string(), "test:2: expected a single line");
+
+        let input = Input::from_string("test", "");
+        assert!(matches!(
+            input.single_line().unwrap_err().kind,
+            ErrorKind::Empty
+        ));
+    }
+
+    #[test]
+    fn test_invalid_utf8() {
+        let e = Input::from_bytes("test", b"abc\n\xff\n".to_vec()).unwrap_err();
+        assert_eq!(e.to_string(), "test:2: line is not valid utf-8");
+    }
+}
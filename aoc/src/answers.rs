@@ -0,0 +1,32 @@
+use std::path::Path;
+
+use aoc_core::Part;
+use serde::{Deserialize, Serialize};
+
+// The known answers of a day, stored in answers.json next to the input.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Answers {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+impl Answers {
+    // A day without an answers file simply has no known answers yet.
+    pub fn load(path: &Path) -> Result<Answers, String> {
+        if !path.exists() {
+            return Ok(Answers::default());
+        }
+
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| format!("{}: unable to read answers: {e}", path.display()))?;
+        serde_json::from_str(&contents)
+            .map_err(|e| format!("{}: invalid answers file: {e}", path.display()))
+    }
+
+    pub fn expected(&self, part: Part) -> Option<&str> {
+        match part {
+            Part::One => self.part1.as_deref(),
+            Part::Two => self.part2.as_deref(),
+        }
+    }
+}
@@ -0,0 +1,207 @@
+use std::hint::black_box;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use aoc_core::Part;
+use aoc_input::Input;
+use serde::{Deserialize, Serialize};
+
+use crate::registry::Day;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Measurement {
+    pub year: u32,
+    pub day: u32,
+    // "parse", "part1" or "part2"
+    pub stage: String,
+    pub median_ns: u64,
+    pub min_ns: u64,
+    pub max_ns: u64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Baseline {
+    pub measurements: Vec<Measurement>,
+}
+
+impl Baseline {
+    pub fn load(path: &Path) -> Result<Baseline, String> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| format!("{}: unable to read baseline: {e}", path.display()))?;
+        serde_json::from_str(&contents)
+            .map_err(|e| format!("{}: invalid baseline: {e}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let contents = serde_json::to_string_pretty(self).expect("baseline is always valid json");
+        std::fs::write(path, contents + "\n")
+            .map_err(|e| format!("{}: unable to write baseline: {e}", path.display()))
+    }
+
+    pub fn find(&self, m: &Measurement) -> Option<&Measurement> {
+        self.measurements
+            .iter()
+            .find(|b| b.year == m.year && b.day == m.day && b.stage == m.stage)
+    }
+
+    // Replaces the stages that were measured again, the other days are kept.
+    pub fn update(&mut self, measurements: Vec<Measurement>) {
+        for m in measurements {
+            match self
+                .measurements
+                .iter_mut()
+                .find(|b| b.year == m.year && b.day == m.day && b.stage == m.stage)
+            {
+                Some(b) => *b = m,
+                None => self.measurements.push(m),
+            }
+        }
+    }
+}
+
+fn measure(year: u32, day: u32, stage: &str, samples: usize, mut f: impl FnMut()) -> Measurement {
+    // warm up the caches before the samples are taken
+    f();
+
+    let mut times: Vec<u64> = (0..samples)
+        .map(|_| {
+            let start = Instant::now();
+            f();
+            start.elapsed().as_nanos() as u64
+        })
+        .collect();
+    times.sort_unstable();
+
+    Measurement {
+        year,
+        day,
+        stage: stage.to_owned(),
+        median_ns: times[times.len() / 2],
+        min_ns: times[0],
+        max_ns: times[times.len() - 1],
+    }
+}
+
+// Times parse, part1 and part2 of the day separately.
+pub fn bench_day(day: &Day, input: &Input, samples: usize) -> Result<Vec<Measurement>, String> {
+    let parsed = day.solution.parse(input).map_err(|errors| {
+        format!(
+            "{} day {}: found {} error(s) in the input.",
+            day.year,
+            day.day,
+            errors.len()
+        )
+    })?;
+
+    let mut measurements = vec![measure(day.year, day.day, "parse", samples, || {
+        let _ = black_box(day.solution.parse(black_box(input)));
+    })];
+    for part in Part::ALL {
+        let stage = format!("part{part}");
+        measurements.push(measure(day.year, day.day, &stage, samples, || {
+            black_box(day.solution.solve(black_box(parsed.as_ref()), part));
+        }));
+    }
+
+    Ok(measurements)
+}
+
+pub fn format_duration(d: Duration) -> String {
+    let ns = d.as_nanos();
+    if ns < 1_000 {
+        format!("{ns} ns")
+    } else if ns < 1_000_000 {
+        format!("{:.2} µs", ns as f64 / 1e3)
+    } else if ns < 1_000_000_000 {
+        format!("{:.2} ms", ns as f64 / 1e6)
+    } else {
+        format!("{:.2} s", ns as f64 / 1e9)
+    }
+}
+
+pub enum Comparison {
+    New,
+    Within(f64),
+    Improved(f64),
+    Regressed(f64),
+}
+
+// The change is in percent, compared against the median of the baseline.
+pub fn compare(
+    current: &Measurement,
+    baseline: Option<&Measurement>,
+    threshold: f64,
+) -> Comparison {
+    let Some(baseline) = baseline else {
+        return Comparison::New;
+    };
+
+    let change = (current.median_ns as f64 / baseline.median_ns.max(1) as f64 - 1.0) * 100.0;
+    if change > threshold {
+        Comparison::Regressed(change)
+    } else if change < -threshold {
+        Comparison::Improved(change)
+    } else {
+        Comparison::Within(change)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bench::*;
+
+    fn measurement(median_ns: u64) -> Measurement {
+        Measurement {
+            year: 2022,
+            day: 1,
+            stage: "parse".to_owned(),
+            median_ns,
+            min_ns: median_ns,
+            max_ns: median_ns,
+        }
+    }
+
+    #[test]
+    fn test_compare() {
+        let baseline = measurement(1000);
+        assert!(matches!(
+            compare(&measurement(1200), Some(&baseline), 10.0),
+            Comparison::Regressed(_)
+        ));
+        assert!(matches!(
+            compare(&measurement(1050), Some(&baseline), 10.0),
+            Comparison::Within(_)
+        ));
+        assert!(matches!(
+            compare(&measurement(500), Some(&baseline), 10.0),
+            Comparison::Improved(_)
+        ));
+        assert!(matches!(
+            compare(&measurement(500), None, 10.0),
+            Comparison::New
+        ));
+    }
+
+    #[test]
+    fn test_update() {
+        let mut other_day = measurement(300);
+        other_day.day = 6;
+        let mut baseline = Baseline {
+            measurements: vec![measurement(1000), other_day.clone()],
+        };
+        let mut part1 = measurement(50);
+        part1.stage = "part1".to_owned();
+        baseline.update(vec![measurement(800), part1.clone()]);
+
+        assert_eq!(baseline.measurements.len(), 3);
+        assert_eq!(baseline.find(&measurement(0)).unwrap().median_ns, 800);
+        assert_eq!(baseline.find(&other_day), Some(&other_day));
+        assert_eq!(baseline.find(&part1), Some(&part1));
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_nanos(512)), "512 ns");
+        assert_eq!(format_duration(Duration::from_micros(1500)), "1.50 ms");
+    }
+}
@@ -0,0 +1,387 @@
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+use aoc_core::{format_error, init_logging, Answer, Part};
+use aoc_input::Input;
+use clap::{Args, Parser, Subcommand};
+
+mod answers;
+mod bench;
+mod output;
+mod registry;
+mod scaffold;
+
+use answers::Answers;
+use bench::{Baseline, Comparison};
+use output::{Format, Record};
+use registry::Day;
+
+#[derive(Parser, Debug)]
+#[command(about = "Runs the advent of code solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+
+    /// Show more logging, -v for info, -vv for debug and -vvv for trace
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    verbose: u8,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Run one puzzle, or all of them with --all
+    Run(RunArgs),
+    /// Check the solutions against the answers in answers.json
+    Verify(VerifyArgs),
+    /// Time parse, part1 and part2 of every day and compare them against a baseline
+    Bench(BenchArgs),
+    /// Create the crate for a new day and register it with the runner
+    NewDay(NewDayArgs),
+}
+
+#[derive(Args, Debug)]
+struct RunArgs {
+    #[arg(short, long, default_value_t = 2022)]
+    year: u32,
+
+    #[arg(short, long, required_unless_present = "all")]
+    day: Option<u32>,
+
+    /// Only run this part, both parts are run if omitted
+    #[arg(short, long, value_parser = clap::value_parser!(u32).range(1..=2))]
+    part: Option<u32>,
+
+    /// Defaults to the input.txt next to the solution, "-" reads from stdin
+    #[arg(short, long, conflicts_with = "all")]
+    input: Option<PathBuf>,
+
+    /// Run every registered day of every year
+    #[arg(short, long, conflicts_with_all = ["day"])]
+    all: bool,
+
+    /// How the answers are printed
+    #[arg(short, long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+}
+
+#[derive(Args, Debug)]
+struct VerifyArgs {
+    /// Only verify this year
+    #[arg(short, long)]
+    year: Option<u32>,
+
+    /// Only verify this day
+    #[arg(short, long)]
+    day: Option<u32>,
+}
+
+#[derive(Args, Debug)]
+struct BenchArgs {
+    /// Only benchmark this year
+    #[arg(short, long)]
+    year: Option<u32>,
+
+    /// Only benchmark this day
+    #[arg(short, long)]
+    day: Option<u32>,
+
+    /// Number of timed runs per stage, the median is reported
+    #[arg(short, long, default_value_t = 20, value_parser = clap::value_parser!(u64).range(1..))]
+    samples: u64,
+
+    /// Baseline to compare against
+    #[arg(short, long, default_value_os_t = default_baseline())]
+    baseline: PathBuf,
+
+    /// Store the results as the new baseline
+    #[arg(long)]
+    save: bool,
+
+    /// Allowed slowdown in percent before it is flagged as a regression
+    #[arg(short, long, default_value_t = 10.0)]
+    threshold: f64,
+}
+
+#[derive(Args, Debug)]
+struct NewDayArgs {
+    #[arg(short, long, default_value_t = 2022)]
+    year: u32,
+
+    #[arg(short, long, value_parser = clap::value_parser!(u32).range(1..=25))]
+    day: u32,
+}
+
+fn default_baseline() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("bench-baseline.json")
+}
+
+fn select_days(year: Option<u32>, day: Option<u32>) -> Vec<&'static Day> {
+    registry::DAYS
+        .iter()
+        .filter(|d| year.is_none_or(|y| y == d.year))
+        .filter(|d| day.is_none_or(|n| n == d.day))
+        .collect()
+}
+
+fn parts_to_run(part: Option<u32>) -> Vec<Part> {
+    match part {
+        Some(p) => vec![Part::from_number(p).expect("part is validated by the argument parser")],
+        None => Part::ALL.to_vec(),
+    }
+}
+
+// Solves the parts, errors are reported on stderr.
+fn solve_day(day: &Day, input: &Path, parts: &[Part]) -> Option<Vec<(Part, Answer, Duration)>> {
+    let input = match Input::from_path_or_stdin(input) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("{e}");
+            return None;
+        }
+    };
+
+    let parsed = match day.solution.parse(&input) {
+        Ok(parsed) => parsed,
+        Err(errors) => {
+            for e in &errors {
+                eprintln!("{}", format_error(&input, e.as_ref()));
+            }
+            eprintln!(
+                "{} day {}: found {} error(s) in the input.",
+                day.year,
+                day.day,
+                errors.len()
+            );
+            return None;
+        }
+    };
+
+    let answers = parts
+        .iter()
+        .map(|p| {
+            let start = Instant::now();
+            let answer = day.solution.solve(parsed.as_ref(), *p);
+            (*p, answer, start.elapsed())
+        })
+        .collect();
+    Some(answers)
+}
+
+fn run_day(day: &Day, part: Option<u32>, input: Option<PathBuf>) -> Option<Vec<Record>> {
+    let input = input.unwrap_or_else(|| day.default_input());
+    let answers = solve_day(day, &input, &parts_to_run(part))?;
+
+    let records = answers
+        .iter()
+        .map(|(p, answer, duration)| Record::new(day.year, day.day, *p, answer, *duration))
+        .collect();
+    Some(records)
+}
+
+fn run(args: RunArgs) -> ExitCode {
+    let mut exit_code = ExitCode::SUCCESS;
+    let mut records = Vec::new();
+    if args.all {
+        for day in registry::DAYS {
+            match run_day(day, args.part, None) {
+                Some(mut r) => records.append(&mut r),
+                None => exit_code = ExitCode::FAILURE,
+            }
+        }
+    } else {
+        let day_number = args.day.expect("day is required without --all");
+        let Some(day) = registry::find(args.year, day_number) else {
+            eprintln!("No solution found for {} day {}.", args.year, day_number);
+            return ExitCode::FAILURE;
+        };
+        match run_day(day, args.part, args.input) {
+            Some(r) => records = r,
+            None => return ExitCode::FAILURE,
+        }
+    }
+
+    if let Err(e) = output::write_records(args.format, &records, std::io::stdout().lock()) {
+        eprintln!("Unable to write the answers: {e}");
+        return ExitCode::FAILURE;
+    }
+
+    exit_code
+}
+
+#[derive(Default)]
+struct VerifySummary {
+    passed: usize,
+    failed: usize,
+    missing: usize,
+}
+
+fn verify_day(day: &Day, summary: &mut VerifySummary) {
+    let answers = match Answers::load(&day.answers_path()) {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("{e}");
+            summary.failed += Part::ALL.len();
+            return;
+        }
+    };
+
+    let Some(solved) = solve_day(day, &day.default_input(), &Part::ALL) else {
+        println!("{} day {}: FAIL unable to solve", day.year, day.day);
+        summary.failed += Part::ALL.len();
+        return;
+    };
+
+    for (p, answer, _) in solved {
+        let answer = answer.to_string();
+        let prefix = format!("{} day {} part {}", day.year, day.day, p);
+        match answers.expected(p) {
+            Some(expected) if expected == answer => {
+                println!("{prefix}: ok ({answer})");
+                summary.passed += 1;
+            }
+            Some(expected) => {
+                println!("{prefix}: FAIL");
+                println!("  expected: {expected}");
+                println!("  actual:   {answer}");
+                summary.failed += 1;
+            }
+            None => {
+                println!("{prefix}: no known answer ({answer})");
+                summary.missing += 1;
+            }
+        }
+    }
+}
+
+fn verify(args: VerifyArgs) -> ExitCode {
+    let days = select_days(args.year, args.day);
+    if days.is_empty() {
+        eprintln!("No solutions match the given year and day.");
+        return ExitCode::FAILURE;
+    }
+
+    let mut summary = VerifySummary::default();
+    for day in days {
+        verify_day(day, &mut summary);
+    }
+
+    println!(
+        "{} passed, {} failed, {} without a known answer.",
+        summary.passed, summary.failed, summary.missing
+    );
+    if summary.failed == 0 {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+fn bench(args: BenchArgs) -> ExitCode {
+    let days = select_days(args.year, args.day);
+    if days.is_empty() {
+        eprintln!("No solutions match the given year and day.");
+        return ExitCode::FAILURE;
+    }
+
+    // Without a baseline everything is new, that is not an error.
+    let baseline = if args.baseline.exists() {
+        match Baseline::load(&args.baseline) {
+            Ok(baseline) => baseline,
+            Err(e) => {
+                eprintln!("{e}");
+                return ExitCode::FAILURE;
+            }
+        }
+    } else {
+        Baseline::default()
+    };
+
+    let mut exit_code = ExitCode::SUCCESS;
+    let mut regressions = 0;
+    let mut results = Vec::new();
+    for day in days {
+        let input = match Input::from_file(&day.default_input()) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("{e}");
+                exit_code = ExitCode::FAILURE;
+                continue;
+            }
+        };
+        let measurements = match bench::bench_day(day, &input, args.samples as usize) {
+            Ok(measurements) => measurements,
+            Err(e) => {
+                eprintln!("{e}");
+                exit_code = ExitCode::FAILURE;
+                continue;
+            }
+        };
+
+        for m in measurements {
+            let median = bench::format_duration(Duration::from_nanos(m.median_ns));
+            let previous = baseline.find(&m);
+            let verdict = match bench::compare(&m, previous, args.threshold) {
+                Comparison::New => "no baseline".to_owned(),
+                Comparison::Within(change) => format!("{change:+.1}%"),
+                Comparison::Improved(change) => format!("{change:+.1}% improved"),
+                Comparison::Regressed(change) => {
+                    regressions += 1;
+                    format!("{change:+.1}% REGRESSION")
+                }
+            };
+            println!(
+                "{} day {} {:<6} {:>10}  ({verdict})",
+                m.year, m.day, m.stage, median
+            );
+            results.push(m);
+        }
+    }
+
+    if args.save {
+        // only the days that were measured are replaced
+        let mut baseline = baseline;
+        baseline.update(results);
+        if let Err(e) = baseline.save(&args.baseline) {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+        println!("Saved the baseline to {}.", args.baseline.display());
+    } else if regressions > 0 {
+        println!(
+            "{regressions} stage(s) are more than {}% slower than the baseline.",
+            args.threshold
+        );
+        exit_code = ExitCode::FAILURE;
+    }
+
+    exit_code
+}
+
+fn new_day(args: NewDayArgs) -> ExitCode {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    match scaffold::create_day(&root, args.year, args.day) {
+        Ok(dir) => {
+            println!("Created {}.", dir.display());
+            println!("Add the puzzle input to input.txt and the example to example.txt.");
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    init_logging(cli.verbose);
+    match cli.command {
+        Command::Run(args) => run(args),
+        Command::Verify(args) => verify(args),
+        Command::Bench(args) => bench(args),
+        Command::NewDay(args) => new_day(args),
+    }
+}
@@ -0,0 +1,117 @@
+use std::io::{self, Write};
+use std::time::Duration;
+
+use aoc_core::{Answer, Part};
+use clap::ValueEnum;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+    Csv,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Record {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    pub answer: String,
+    // in seconds, only the time spend in the part itself, parsing is not included
+    pub duration: f64,
+}
+
+impl Record {
+    pub fn new(year: u32, day: u32, part: Part, answer: &Answer, duration: Duration) -> Record {
+        Record {
+            year,
+            day,
+            part: part.number(),
+            answer: answer.to_string(),
+            duration: duration.as_secs_f64(),
+        }
+    }
+}
+
+pub fn write_records(format: Format, records: &[Record], mut out: impl Write) -> io::Result<()> {
+    match format {
+        Format::Text => {
+            for r in records {
+                writeln!(
+                    out,
+                    "{} day {} part {}: {}",
+                    r.year, r.day, r.part, r.answer
+                )?;
+            }
+        }
+        Format::Json => {
+            serde_json::to_writer_pretty(&mut out, records)?;
+            writeln!(out)?;
+        }
+        Format::Csv => {
+            let mut writer = csv::Writer::from_writer(out);
+            for r in records {
+                writer.serialize(r)?;
+            }
+            writer.flush()?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::output::*;
+
+    fn records() -> Vec<Record> {
+        vec![
+            Record::new(
+                2022,
+                1,
+                Part::One,
+                &Answer::from(24000),
+                Duration::from_millis(2),
+            ),
+            Record::new(
+                2022,
+                5,
+                Part::Two,
+                &Answer::from("MCD"),
+                Duration::from_millis(1),
+            ),
+        ]
+    }
+
+    fn write(format: Format) -> String {
+        let mut buffer = Vec::new();
+        write_records(format, &records(), &mut buffer).unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    #[test]
+    fn test_text() {
+        assert_eq!(
+            write(Format::Text),
+            "2022 day 1 part 1: 24000\n2022 day 5 part 2: MCD\n"
+        );
+    }
+
+    #[test]
+    fn test_csv() {
+        assert_eq!(
+            write(Format::Csv),
+            "year,day,part,answer,duration\n2022,1,1,24000,0.002\n2022,5,2,MCD,0.001\n"
+        );
+    }
+
+    #[test]
+    fn test_json() {
+        let parsed: serde_json::Value = serde_json::from_str(&write(Format::Json)).unwrap();
+        assert_eq!(parsed[1]["answer"], "MCD");
+        assert_eq!(parsed[0]["part"], 1);
+        assert_eq!(parsed[0]["duration"], 0.002);
+    }
+}
@@ -0,0 +1,69 @@
+use std::path::{Path, PathBuf};
+
+use aoc_core::AnySolution;
+
+pub struct Day {
+    pub year: u32,
+    pub day: u32,
+    pub solution: &'static dyn AnySolution,
+}
+
+impl Day {
+    pub fn dir(&self) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join(self.year.to_string())
+            .join(format!("day{}", self.day))
+    }
+
+    // The puzzle input that is checked in next to the solution.
+    pub fn default_input(&self) -> PathBuf {
+        self.dir().join("input.txt")
+    }
+
+    pub fn answers_path(&self) -> PathBuf {
+        self.dir().join("answers.json")
+    }
+}
+
+pub const DAYS: &[Day] = &[
+    Day {
+        year: 2022,
+        day: 1,
+        solution: &day1::Day1,
+    },
+    Day {
+        year: 2022,
+        day: 2,
+        solution: &day2::Day2,
+    },
+    Day {
+        year: 2022,
+        day: 3,
+        solution: &day3::Day3,
+    },
+    Day {
+        year: 2022,
+        day: 4,
+        solution: &day4::Day4,
+    },
+    Day {
+        year: 2022,
+        day: 5,
+        solution: &day5::Day5,
+    },
+    Day {
+        year: 2022,
+        day: 6,
+        solution: &day6::Day6,
+    },
+    Day {
+        year: 2022,
+        day: 7,
+        solution: &day7::Day7,
+    },
+];
+
+pub fn find(year: u32, day: u32) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.year == year && d.day == day)
+}
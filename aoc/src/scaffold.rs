@@ -0,0 +1,176 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const CARGO_TOML: &str = include_str!("../templates/Cargo.toml.tmpl");
+const LIB_RS: &str = include_str!("../templates/lib.rs.tmpl");
+const ERROR_RS: &str = include_str!("../templates/error.rs.tmpl");
+const MAIN_RS: &str = include_str!("../templates/main.rs.tmpl");
+
+// The names used for a new day, the crate is only called "dayN" when no other
+// year has a crate with that name already.
+#[derive(Debug, PartialEq, Eq)]
+pub struct NewDay {
+    pub year: u32,
+    pub day: u32,
+    pub crate_name: String,
+}
+
+impl NewDay {
+    pub fn new(year: u32, day: u32, workspace_manifest: &str) -> NewDay {
+        let taken = workspace_manifest.lines().any(|l| {
+            l.trim()
+                .trim_matches(['"', ','])
+                .ends_with(&format!("/day{day}"))
+        });
+        let crate_name = if taken {
+            format!("day{day}-{year}")
+        } else {
+            format!("day{day}")
+        };
+        NewDay {
+            year,
+            day,
+            crate_name,
+        }
+    }
+
+    fn member(&self) -> String {
+        format!("{}/day{}", self.year, self.day)
+    }
+
+    fn crate_ident(&self) -> String {
+        self.crate_name.replace('-', "_")
+    }
+
+    fn render(&self, template: &str) -> String {
+        template
+            .replace("{{crate}}", &self.crate_name)
+            .replace("{{crate_ident}}", &self.crate_ident())
+            .replace("{{Day}}", &format!("Day{}", self.day))
+    }
+}
+
+// Adds the new crate to the members of the workspace.
+pub fn add_member(manifest: &str, new_day: &NewDay) -> Result<String, String> {
+    let start = manifest
+        .find("members = [")
+        .ok_or("no members list in the workspace Cargo.toml")?;
+    let end = start
+        + manifest[start..]
+            .find("\n]")
+            .ok_or("the members list in the workspace Cargo.toml is not closed")?;
+
+    let mut updated = manifest.to_owned();
+    updated.insert_str(end, &format!("\n    \"{}\",", new_day.member()));
+    Ok(updated)
+}
+
+// Adds the path dependency to the Cargo.toml of the runner.
+pub fn add_dependency(manifest: &str, new_day: &NewDay) -> String {
+    let mut updated = manifest.trim_end().to_owned();
+    updated.push_str(&format!(
+        "\n{} = {{path=\"../{}\"}}\n",
+        new_day.crate_name,
+        new_day.member()
+    ));
+    updated
+}
+
+// Adds the solution to the end of the DAYS list of the registry.
+pub fn add_registration(registry: &str, new_day: &NewDay) -> Result<String, String> {
+    let start = registry
+        .find("pub const DAYS")
+        .ok_or("no DAYS list in the registry")?;
+    let end = start
+        + registry[start..]
+            .find("\n];")
+            .ok_or("the DAYS list in the registry is not closed")?;
+
+    let entry = format!(
+        "\n    Day {{\n        year: {},\n        day: {},\n        solution: &{}::Day{},\n    }},",
+        new_day.year,
+        new_day.day,
+        new_day.crate_ident(),
+        new_day.day
+    );
+    let mut updated = registry.to_owned();
+    updated.insert_str(end, &entry);
+    Ok(updated)
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), String> {
+    fs::write(path, contents).map_err(|e| format!("{}: unable to write: {e}", path.display()))
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|e| format!("{}: unable to read: {e}", path.display()))
+}
+
+// Creates <year>/day<N> in the workspace and registers it with the runner.
+// Returns the directory of the new day.
+pub fn create_day(root: &Path, year: u32, day: u32) -> Result<PathBuf, String> {
+    let workspace_manifest = root.join("Cargo.toml");
+    let runner_manifest = root.join("aoc").join("Cargo.toml");
+    let registry = root.join("aoc").join("src").join("registry.rs");
+
+    let manifest = read(&workspace_manifest)?;
+    let new_day = NewDay::new(year, day, &manifest);
+    let dir = root.join(new_day.member());
+    if dir.exists() {
+        return Err(format!("{}: already exists", dir.display()));
+    }
+
+    // Everything is prepared first, so a failure does not leave a half registered day.
+    let manifest = add_member(&manifest, &new_day)?;
+    let runner = add_dependency(&read(&runner_manifest)?, &new_day);
+    let registry_source = add_registration(&read(&registry)?, &new_day)?;
+
+    let src = dir.join("src");
+    fs::create_dir_all(&src).map_err(|e| format!("{}: unable to create: {e}", src.display()))?;
+    write(&dir.join("Cargo.toml"), &new_day.render(CARGO_TOML))?;
+    write(&dir.join(".gitignore"), "target\n")?;
+    write(&dir.join("input.txt"), "")?;
+    write(&dir.join("example.txt"), "")?;
+    write(&dir.join("answers.json"), "{}\n")?;
+    write(&src.join("lib.rs"), &new_day.render(LIB_RS))?;
+    write(&src.join("error.rs"), &new_day.render(ERROR_RS))?;
+    write(&src.join("main.rs"), &new_day.render(MAIN_RS))?;
+
+    write(&workspace_manifest, &manifest)?;
+    write(&runner_manifest, &runner)?;
+    write(&registry, &registry_source)?;
+
+    Ok(dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::scaffold::*;
+
+    const MANIFEST: &str = "[workspace]\nmembers = [\n    \"aoc\",\n    \"2022/day1\",\n]\n";
+
+    #[test]
+    fn test_crate_name() {
+        assert_eq!(NewDay::new(2022, 2, MANIFEST).crate_name, "day2");
+        assert_eq!(NewDay::new(2023, 1, MANIFEST).crate_name, "day1-2023");
+    }
+
+    #[test]
+    fn test_add_member() {
+        let new_day = NewDay::new(2022, 2, MANIFEST);
+        assert_eq!(
+            add_member(MANIFEST, &new_day).unwrap(),
+            "[workspace]\nmembers = [\n    \"aoc\",\n    \"2022/day1\",\n    \"2022/day2\",\n]\n"
+        );
+    }
+
+    #[test]
+    fn test_add_registration() {
+        let registry = "pub const DAYS: &[Day] = &[\n];\n";
+        let new_day = NewDay::new(2023, 1, MANIFEST);
+        assert_eq!(
+            add_registration(registry, &new_day).unwrap(),
+            "pub const DAYS: &[Day] = &[\n    Day {\n        year: 2023,\n        day: 1,\n        solution: &day1_2023::Day1,\n    },\n];\n"
+        );
+    }
+}